@@ -0,0 +1,60 @@
+use common::frame_render::{Palette, Rgb};
+
+/// One acre of the day 18 lumber collection area
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Acre {
+    Ground,
+    Trees,
+    Lumberyard,
+}
+
+impl Acre {
+    pub fn tick(self, neighbors: impl Iterator<Item = Acre>) -> Acre {
+        match self {
+            Acre::Ground => {
+                if neighbors.filter(|a| *a == Acre::Trees).count() >= 3 {
+                    Acre::Trees
+                } else {
+                    self
+                }
+            }
+
+            Acre::Trees => {
+                if neighbors.filter(|a| *a == Acre::Lumberyard).count() >= 3 {
+                    Acre::Lumberyard
+                } else {
+                    self
+                }
+            }
+            Acre::Lumberyard => {
+                let mut has_trees = false;
+                let mut has_lumbers = false;
+                for n in neighbors {
+                    match n {
+                        Acre::Lumberyard => has_lumbers = true,
+                        Acre::Trees => has_trees = true,
+                        _ => continue,
+                    }
+                    if has_trees && has_lumbers {
+                        break;
+                    }
+                }
+                if !(has_trees && has_lumbers) {
+                    Acre::Ground
+                } else {
+                    self
+                }
+            }
+        }
+    }
+}
+
+impl Palette for Acre {
+    fn color(&self) -> Rgb {
+        match self {
+            Acre::Ground => [0xc2, 0xb2, 0x80],
+            Acre::Lumberyard => [0x6b, 0x3e, 0x1e],
+            Acre::Trees => [0x22, 0x8b, 0x22],
+        }
+    }
+}
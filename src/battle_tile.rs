@@ -0,0 +1,54 @@
+use common::frame_render::{Palette, Rgb};
+
+/// A square of the day 15 cave
+#[derive(Debug, Clone)]
+pub enum Tile {
+    Open,
+    Wall,
+    Unit(Unit),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Unit {
+    pub health: u8,
+    pub id: usize,
+    pub kind: UnitKind,
+}
+
+impl Unit {
+    pub fn is_enemy(&self, other: &Self) -> bool {
+        match (&self.kind, &other.kind) {
+            (UnitKind::Goblin, UnitKind::Elf) => true,
+            (UnitKind::Elf, UnitKind::Goblin) => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum UnitKind {
+    Goblin,
+    Elf,
+}
+
+impl Tile {
+    pub fn is_goblin(&self) -> bool {
+        match self {
+            Tile::Unit(u) => u.kind == UnitKind::Goblin,
+            _ => false,
+        }
+    }
+}
+
+impl Palette for Tile {
+    fn color(&self) -> Rgb {
+        match self {
+            Tile::Wall => [0x40, 0x40, 0x40],
+            Tile::Open => [0xd0, 0xd0, 0xd0],
+            Tile::Unit(u) => match u.kind {
+                UnitKind::Goblin => [0xc0, 0x20, 0x20],
+                UnitKind::Elf => [0x20, 0xa0, 0x40],
+            },
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+};
+
+/// Index over box IDs for Hamming distance queries up to a fixed `max_distance`.
+///
+/// Every ID is split into `max_distance + 1` segments; two IDs of the same length within
+/// `max_distance` of each other must then share at least one segment verbatim, so only IDs
+/// in the same segment bucket need to be compared.
+pub struct SimilarityIndex<'a> {
+    ids: Vec<&'a str>,
+    chars: Vec<Vec<char>>,
+    max_distance: usize,
+    buckets: HashMap<(usize, usize, Vec<char>), Vec<usize>>,
+}
+
+impl<'a> SimilarityIndex<'a> {
+    pub fn new(ids: impl IntoIterator<Item = &'a str>, max_distance: usize) -> Self {
+        let ids: Vec<_> = ids.into_iter().collect();
+        let chars: Vec<Vec<_>> = ids.iter().map(|id| id.chars().collect()).collect();
+        let mut buckets: HashMap<_, Vec<_>> = HashMap::new();
+        chars.iter().enumerate().for_each(|(i, c)| {
+            segments(c, max_distance).for_each(|key| buckets.entry(key).or_default().push(i));
+        });
+
+        SimilarityIndex {
+            ids,
+            chars,
+            max_distance,
+            buckets,
+        }
+    }
+
+    /// All pairs of IDs at most `distance` apart, in input order. Pairs further apart than the
+    /// index was built for could be missed, so those distances are rejected.
+    pub fn pairs_within(
+        &self,
+        distance: usize,
+    ) -> Result<Vec<(&'a str, &'a str, usize)>, DistanceTooLarge> {
+        if distance > self.max_distance {
+            return Err(DistanceTooLarge {
+                requested: distance,
+                max_distance: self.max_distance,
+            });
+        }
+
+        let pairs: BTreeMap<_, _> = self
+            .candidate_pairs()
+            .filter_map(|(a, b)| {
+                let d = hamming(&self.chars[a], &self.chars[b])?;
+                if d <= distance {
+                    Some(((a, b), d))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Ok(pairs
+            .into_iter()
+            .map(|((a, b), d)| (self.ids[a], self.ids[b], d))
+            .collect())
+    }
+
+    /// The closest indexed ID of the same length, preferring earlier IDs on ties. IDs sharing no
+    /// segment with `id` are only scanned when none within `max_distance` shares one.
+    pub fn nearest(&self, id: &str) -> Option<(&'a str, usize)> {
+        let chars: Vec<_> = id.chars().collect();
+        let closest = |candidates: &mut dyn Iterator<Item = usize>| {
+            candidates
+                .filter_map(|i| hamming(&chars, &self.chars[i]).map(|d| (d, i)))
+                .min()
+        };
+
+        let mut candidates = segments(&chars, self.max_distance)
+            .filter_map(|key| self.buckets.get(&key))
+            .flat_map(|bucket| bucket.iter().cloned());
+
+        closest(&mut candidates)
+            .filter(|&(d, _)| d <= self.max_distance)
+            .or_else(|| closest(&mut (0..self.ids.len())))
+            .map(|(d, i)| (self.ids[i], d))
+    }
+
+    /// IDs that differ from each other only at the same single position, keyed by the letters they
+    /// have in common. Groups come in the order the input completes their first pair, so the
+    /// first group holds the earliest ID that has a match before it.
+    pub fn signature_groups(&self) -> Result<Vec<(String, Vec<&'a str>)>, DistanceTooLarge> {
+        if self.max_distance == 0 {
+            return Err(DistanceTooLarge {
+                requested: 1,
+                max_distance: 0,
+            });
+        }
+
+        let mut groups: HashMap<(String, usize), (usize, BTreeSet<usize>)> = HashMap::new();
+        self.candidate_pairs()
+            .filter(|&(a, b)| hamming(&self.chars[a], &self.chars[b]) == Some(1))
+            .for_each(|(a, b)| {
+                let (a_chars, b_chars) = (&self.chars[a], &self.chars[b]);
+                let position = a_chars.iter().zip(b_chars).position(|(x, y)| x != y);
+                let common = a_chars
+                    .iter()
+                    .zip(b_chars)
+                    .filter_map(|(x, y)| if x == y { Some(x) } else { None })
+                    .collect();
+                let (completed, group) = groups
+                    .entry((common, position.unwrap()))
+                    .or_insert_with(|| (a.max(b), BTreeSet::new()));
+                *completed = (*completed).min(a.max(b));
+                group.insert(a);
+                group.insert(b);
+            });
+
+        let mut groups: Vec<_> = groups.into_iter().collect();
+        groups
+            .sort_unstable_by_key(|(_, (completed, ids))| (*completed, ids.iter().next().cloned()));
+        Ok(groups
+            .into_iter()
+            .map(|((common, _), (_, ids))| (common, ids.into_iter().map(|i| self.ids[i]).collect()))
+            .collect())
+    }
+
+    /// Pairs sharing at least one segment, possibly more than once
+    fn candidate_pairs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.buckets.values().flat_map(|bucket| {
+            bucket
+                .iter()
+                .enumerate()
+                .flat_map(move |(n, &a)| bucket[n + 1..].iter().map(move |&b| (a, b)))
+        })
+    }
+}
+
+/// A query needs IDs further apart than the index was built for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DistanceTooLarge {
+    pub requested: usize,
+    pub max_distance: usize,
+}
+
+impl fmt::Display for DistanceTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "distance {} is above the {} the index was built for",
+            self.requested, self.max_distance
+        )
+    }
+}
+
+fn segments(
+    chars: &[char],
+    max_distance: usize,
+) -> impl Iterator<Item = (usize, usize, Vec<char>)> + '_ {
+    let parts = max_distance + 1;
+    let len = chars.len();
+    (0..parts).map(move |p| {
+        let segment = chars[p * len / parts..(p + 1) * len / parts].to_vec();
+        (len, p, segment)
+    })
+}
+
+fn hamming(a: &[char], b: &[char]) -> Option<usize> {
+    if a.len() == b.len() {
+        Some(a.iter().zip(b).filter(|(x, y)| x != y).count())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDS: &[&str] = &[
+        "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+    ];
+
+    #[test]
+    fn pairs() {
+        let index = SimilarityIndex::new(IDS.iter().cloned(), 2);
+        assert_eq!(index.pairs_within(1), Ok(vec![("fghij", "fguij", 1)]));
+        assert_eq!(
+            index.pairs_within(2),
+            Ok(vec![("abcde", "axcye", 2), ("fghij", "fguij", 1)])
+        );
+        assert_eq!(
+            index.pairs_within(3),
+            Err(DistanceTooLarge {
+                requested: 3,
+                max_distance: 2
+            })
+        );
+    }
+
+    #[test]
+    fn nearest() {
+        let index = SimilarityIndex::new(IDS.iter().cloned(), 1);
+        assert_eq!(index.nearest("fghix"), Some(("fghij", 1)));
+        assert_eq!(index.nearest("abxxe"), Some(("abcde", 2)));
+        assert_eq!(index.nearest("abc"), None);
+
+        // the only bucket hit is further away than an ID sharing no segment
+        let index = SimilarityIndex::new(vec!["aaaaxxxx", "abaayyzy"], 1);
+        assert_eq!(index.nearest("aaaayyyy"), Some(("abaayyzy", 2)));
+    }
+
+    #[test]
+    fn groups() {
+        let ids = ["abcd", "abxd", "abyd", "zbcd", "qqqq"];
+        let index = SimilarityIndex::new(ids.iter().cloned(), 1);
+        assert_eq!(
+            index.signature_groups(),
+            Ok(vec![
+                ("abd".to_string(), vec!["abcd", "abxd", "abyd"]),
+                ("bcd".to_string(), vec!["abcd", "zbcd"]),
+            ])
+        );
+
+        let ids = ["bb", "bc", "xa", "ya", "bd"];
+        let index = SimilarityIndex::new(ids.iter().cloned(), 1);
+        assert_eq!(
+            index.signature_groups(),
+            Ok(vec![
+                ("b".to_string(), vec!["bb", "bc", "bd"]),
+                ("a".to_string(), vec!["xa", "ya"]),
+            ])
+        );
+
+        let exact = SimilarityIndex::new(ids.iter().cloned(), 0);
+        assert_eq!(
+            exact.signature_groups(),
+            Err(DistanceTooLarge {
+                requested: 1,
+                max_distance: 0
+            })
+        );
+    }
+}
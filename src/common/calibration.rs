@@ -0,0 +1,60 @@
+use std::{
+    fmt,
+    io::{self, BufRead},
+    num::ParseIntError,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Malformed { line: usize, error: ParseIntError },
+    Overflow { line: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "failed to read input: {}", e),
+            Error::Malformed { line, error } => write!(f, "line {}: {}", line, error),
+            Error::Overflow { line } => write!(f, "line {}: frequency overflowed", line),
+        }
+    }
+}
+
+/// Parses one frequency change per line without holding more than a line in memory, blank lines are skipped
+pub fn changes<R: BufRead>(reader: R) -> impl Iterator<Item = Result<(usize, i64), Error>> {
+    reader
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l))
+        .filter(|(_, l)| l.as_ref().map_or(true, |l| !l.trim().is_empty()))
+        .map(|(line, l)| {
+            let change = l
+                .map_err(Error::Io)?
+                .trim()
+                .parse()
+                .map_err(|error| Error::Malformed { line, error })?;
+            Ok((line, change))
+        })
+}
+
+/// Part one: the resulting frequency after applying every change once
+pub fn frequency<R: BufRead>(reader: R) -> Result<i64, Error> {
+    changes(reader).try_fold(0i64, |sum, change| {
+        let (line, change) = change?;
+        sum.checked_add(change).ok_or(Error::Overflow { line })
+    })
+}
+
+/// Part two needs every change to cycle through them, the running frequency is still checked so that
+/// partial sums over the returned changes always fit in an `i64`
+pub fn collect_changes<R: BufRead>(reader: R) -> Result<Vec<i64>, Error> {
+    let mut sum = 0i64;
+    changes(reader)
+        .map(|change| {
+            let (line, change) = change?;
+            sum = sum.checked_add(change).ok_or(Error::Overflow { line })?;
+            Ok(change)
+        })
+        .collect()
+}
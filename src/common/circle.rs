@@ -0,0 +1,180 @@
+/// A circular doubly linked list stored in a `Vec`, with a cursor on the current element.
+/// Moving the cursor is O(1) per step, inserting and removing at the cursor are O(1).
+#[derive(Debug, Clone, Default)]
+pub struct Circle<T> {
+    nodes: Vec<Node<T>>,
+    current: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    prev: usize,
+    next: usize,
+    value: T,
+}
+
+impl<T> Circle<T> {
+    pub fn new() -> Self {
+        Circle::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Circle {
+            nodes: Vec::with_capacity(capacity),
+            current: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn current(&self) -> Option<&T> {
+        self.nodes.get(self.current).map(|n| &n.value)
+    }
+
+    pub fn clockwise(&mut self, steps: usize) -> &mut Self {
+        if !self.is_empty() {
+            self.current = (0..steps).fold(self.current, |i, _| self.nodes[i].next);
+        }
+        self
+    }
+
+    pub fn counter_clockwise(&mut self, steps: usize) -> &mut Self {
+        if !self.is_empty() {
+            self.current = (0..steps).fold(self.current, |i, _| self.nodes[i].prev);
+        }
+        self
+    }
+
+    /// Places `value` clockwise next to the current element and makes it the current one
+    pub fn insert_after(&mut self, value: T) -> &mut Self {
+        let inserted = self.nodes.len();
+        if self.is_empty() {
+            self.nodes.push(Node {
+                prev: inserted,
+                next: inserted,
+                value,
+            });
+        } else {
+            let prev = self.current;
+            let next = self.nodes[prev].next;
+            self.nodes.push(Node { prev, next, value });
+            self.nodes[prev].next = inserted;
+            self.nodes[next].prev = inserted;
+        }
+        self.current = inserted;
+        self
+    }
+
+    /// Takes out the current element, the one clockwise from it becomes the current one
+    pub fn remove(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let removed = self.current;
+        let (prev, next) = (self.nodes[removed].prev, self.nodes[removed].next);
+        self.nodes[prev].next = next;
+        self.nodes[next].prev = prev;
+        let value = self.nodes.swap_remove(removed).value;
+
+        // the last node took the removed node's slot, point its neighbours to where it is now
+        let moved = self.nodes.len();
+        let relocate = |i: usize| if i == moved { removed } else { i };
+        self.current = relocate(next);
+        if removed < moved {
+            let prev = relocate(self.nodes[removed].prev);
+            let next = relocate(self.nodes[removed].next);
+            self.nodes[removed].prev = prev;
+            self.nodes[removed].next = next;
+            self.nodes[prev].next = removed;
+            self.nodes[next].prev = removed;
+        }
+        Some(value)
+    }
+
+    /// Every element once, clockwise starting from the current one
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut at = self.current;
+        self.nodes.iter().map(move |_| {
+            let node = &self.nodes[at];
+            at = node.next;
+            &node.value
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_rng::Lcg;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn cursor() {
+        let mut circle = Circle::new();
+        assert_eq!(circle.remove(), None::<u32>);
+        circle
+            .insert_after(0)
+            .insert_after(1)
+            .clockwise(1)
+            .insert_after(2);
+        assert_eq!(circle.iter().cloned().collect::<Vec<_>>(), vec![2, 1, 0]);
+        assert_eq!(circle.counter_clockwise(1).current(), Some(&0));
+        assert_eq!(circle.remove(), Some(0));
+        assert_eq!(circle.current(), Some(&2));
+        assert_eq!(circle.remove(), Some(2));
+        assert_eq!(circle.iter().cloned().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(circle.remove(), Some(1));
+        assert!(circle.is_empty());
+        assert_eq!(circle.current(), None);
+    }
+
+    /// The front of the deque is the current element, clockwise is towards the back
+    #[test]
+    fn matches_deque() {
+        let mut circle = Circle::new();
+        let mut deque = VecDeque::new();
+        let mut rng = Lcg::new(3);
+        for value in 0..5_000 {
+            let steps = rng.below(5) as usize;
+            match rng.below(4) {
+                0 => {
+                    circle.clockwise(steps);
+                    if !deque.is_empty() {
+                        (0..steps).for_each(|_| {
+                            let front = deque.pop_front().unwrap();
+                            deque.push_back(front);
+                        });
+                    }
+                }
+                1 => {
+                    circle.counter_clockwise(steps);
+                    if !deque.is_empty() {
+                        (0..steps).for_each(|_| {
+                            let back = deque.pop_back().unwrap();
+                            deque.push_front(back);
+                        });
+                    }
+                }
+                2 => {
+                    circle.insert_after(value);
+                    if let Some(front) = deque.pop_front() {
+                        deque.push_back(front);
+                    }
+                    deque.push_front(value);
+                }
+                _ => assert_eq!(circle.remove(), deque.pop_front()),
+            }
+            assert_eq!(circle.current(), deque.front());
+            assert_eq!(circle.len(), deque.len());
+        }
+        assert!(circle.iter().eq(deque.iter()));
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::ParseIntError,
+    str::FromStr,
+};
+
+/// A rectangle of fabric, `right` and `bottom` are exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Claim {
+    pub id: u32,
+    pub left: i64,
+    pub top: i64,
+    pub right: i64,
+    pub bottom: i64,
+}
+
+impl Claim {
+    pub fn area(&self) -> u64 {
+        ((self.right - self.left) * (self.bottom - self.top)) as u64
+    }
+
+    /// Area shared with `other`, `None` if they do not share a single square inch
+    pub fn overlap(&self, other: &Claim) -> Option<u64> {
+        let width = self.right.min(other.right) - self.left.max(other.left);
+        let height = self.bottom.min(other.bottom) - self.top.max(other.top);
+        if width > 0 && height > 0 {
+            Some((width * height) as u64)
+        } else {
+            None
+        }
+    }
+}
+
+/// Claims over a fabric of any size, nothing is allocated per square inch
+pub struct Fabric {
+    claims: Vec<Claim>,
+}
+
+impl Fabric {
+    pub fn new(claims: Vec<Claim>) -> Self {
+        Fabric { claims }
+    }
+
+    /// Square inches within two or more claims
+    pub fn overlap_area(&self) -> u64 {
+        let mut area = 0;
+        self.sweep(|width, ys, depths| {
+            let overlapped: i64 = depths
+                .iter()
+                .enumerate()
+                .filter(|(_, &d)| d >= 2)
+                .map(|(i, _)| ys[i + 1] - ys[i])
+                .sum();
+            area += (overlapped * width) as u64;
+        });
+        area
+    }
+
+    /// Largest number of claims covering the same square inch
+    pub fn max_depth(&self) -> u32 {
+        let mut max = 0;
+        self.sweep(|_, _, depths| {
+            max = depths.iter().cloned().fold(max, i32::max);
+        });
+        max as u32
+    }
+
+    /// Visits every vertical strip between two consecutive claim edges with its width, the
+    /// compressed y coordinates and how many claims cover each band between them
+    fn sweep(&self, mut visit: impl FnMut(i64, &[i64], &[i32])) {
+        let claims: Vec<_> = self.claims.iter().filter(|c| c.area() > 0).collect();
+        let mut ys: Vec<_> = claims.iter().flat_map(|c| vec![c.top, c.bottom]).collect();
+        ys.sort_unstable();
+        ys.dedup();
+
+        // ends sort before starts so that claims touching at an edge never share a column
+        let mut events: Vec<_> = claims
+            .iter()
+            .flat_map(|c| vec![(c.left, 1, c), (c.right, -1, c)])
+            .collect();
+        events.sort_unstable_by_key(|&(x, delta, _)| (x, delta));
+
+        let mut depths = vec![0; ys.len().saturating_sub(1)];
+        let band = |y: i64| ys.binary_search(&y).unwrap();
+        let mut last_x = events.first().map_or(0, |&(x, _, _)| x);
+
+        for (x, delta, claim) in events {
+            if x > last_x {
+                visit(x - last_x, &ys, &depths);
+                last_x = x;
+            }
+
+            depths[band(claim.top)..band(claim.bottom)]
+                .iter_mut()
+                .for_each(|d| *d += delta);
+        }
+    }
+
+    pub fn overlap_graph(&self) -> OverlapGraph {
+        let mut overlaps: BTreeMap<_, Vec<_>> =
+            self.claims.iter().map(|c| (c.id, vec![])).collect();
+
+        let mut sorted: Vec<_> = self.claims.iter().collect();
+        sorted.sort_unstable_by_key(|c| c.left);
+        sorted.iter().enumerate().for_each(|(i, a)| {
+            sorted[i + 1..]
+                .iter()
+                .take_while(|b| b.left < a.right)
+                .filter_map(|b| a.overlap(b).map(|area| (b, area)))
+                .for_each(|(b, area)| {
+                    overlaps.get_mut(&a.id).unwrap().push((b.id, area));
+                    overlaps.get_mut(&b.id).unwrap().push((a.id, area));
+                });
+        });
+        overlaps.values_mut().for_each(|o| o.sort_unstable());
+
+        OverlapGraph { overlaps }
+    }
+
+    /// Claims that do not overlap any other claim
+    pub fn clean_ids(&self) -> Vec<u32> {
+        self.overlap_graph()
+            .overlaps
+            .into_iter()
+            .filter(|(_, o)| o.is_empty())
+            .map(|(id, _)| id)
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct OverlapGraph {
+    overlaps: BTreeMap<u32, Vec<(u32, u64)>>,
+}
+
+impl OverlapGraph {
+    /// Claims overlapping `id` and the area shared with each of them
+    pub fn overlaps(&self, id: u32) -> &[(u32, u64)] {
+        self.overlaps.get(&id).map_or(&[], |o| o.as_slice())
+    }
+
+    /// Groups of claims connected through overlaps, a clean claim is a group on its own
+    pub fn components(&self) -> Vec<Vec<u32>> {
+        let mut visited = BTreeSet::new();
+        self.overlaps
+            .keys()
+            .filter_map(|&start| {
+                if !visited.insert(start) {
+                    return None;
+                }
+                let mut component = vec![];
+                let mut pending = vec![start];
+                while let Some(id) = pending.pop() {
+                    component.push(id);
+                    pending.extend(
+                        self.overlaps(id)
+                            .iter()
+                            .map(|&(other, _)| other)
+                            .filter(|&other| visited.insert(other)),
+                    );
+                }
+                component.sort_unstable();
+                Some(component)
+            })
+            .collect()
+    }
+
+    /// Every overlapping pair once, with the lower ID first, and the area they share
+    pub fn edges(&self) -> impl Iterator<Item = (u32, u32, u64)> + '_ {
+        self.overlaps.iter().flat_map(|(&a, o)| {
+            o.iter()
+                .filter(move |&&(b, _)| a < b)
+                .map(move |&(b, area)| (a, b, area))
+        })
+    }
+}
+
+impl FromStr for Claim {
+    type Err = ClaimParseError;
+
+    /// Parses `#<id> @ <left>,<top>: <width>x<height>`, offsets may be negative
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut line = line.split('@');
+        let id = line.next().ok_or(ClaimParseError)?.trim();
+        let id = id.get(1..).ok_or(ClaimParseError)?.parse()?;
+        let mut line = line.next().ok_or(ClaimParseError)?.split(':');
+        let mut top_left = line.next().ok_or(ClaimParseError)?.trim().split(',');
+        let left: i64 = top_left.next().ok_or(ClaimParseError)?.trim().parse()?;
+        let top: i64 = top_left.next().ok_or(ClaimParseError)?.trim().parse()?;
+        let mut size = line.next().ok_or(ClaimParseError)?.trim().split('x');
+        let width: u32 = size.next().ok_or(ClaimParseError)?.parse()?;
+        let height: u32 = size.next().ok_or(ClaimParseError)?.parse()?;
+        Ok(Claim {
+            id,
+            left,
+            top,
+            right: left + i64::from(width),
+            bottom: top + i64::from(height),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ClaimParseError;
+
+impl From<ParseIntError> for ClaimParseError {
+    fn from(_: ParseIntError) -> Self {
+        ClaimParseError
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fabric(input: &str) -> Fabric {
+        Fabric::new(input.lines().map(|l| l.parse().unwrap()).collect())
+    }
+
+    #[test]
+    fn sample() {
+        let fabric = fabric(
+            r"#1 @ 1,3: 4x4
+#2 @ 3,1: 4x4
+#3 @ 5,5: 2x2",
+        );
+        assert_eq!(fabric.overlap_area(), 4);
+        assert_eq!(fabric.clean_ids(), vec![3]);
+        assert_eq!(
+            fabric.overlap_graph().edges().collect::<Vec<_>>(),
+            vec![(1, 2, 4)]
+        );
+    }
+
+    #[test]
+    fn conflicts() {
+        let fabric = fabric(
+            r"#1 @ 1,3: 4x4
+#2 @ 3,1: 4x4
+#3 @ 5,5: 2x2
+#4 @ 2,2: 2x2
+#5 @ 0,0: 0x9",
+        );
+        let graph = fabric.overlap_graph();
+        assert_eq!(graph.overlaps(1), &[(2, 4), (4, 2)]);
+        assert_eq!(graph.overlaps(3), &[]);
+        assert_eq!(graph.overlaps(9), &[]);
+        assert_eq!(graph.components(), vec![vec![1, 2, 4], vec![3], vec![5]]);
+        assert_eq!(fabric.max_depth(), 3);
+        assert_eq!(fabric.overlap_area(), 6);
+    }
+
+    #[test]
+    fn large_and_negative() {
+        let fabric = fabric(
+            r"#1 @ -5,-5: 10x10
+#2 @ 0,0: 3000000x2
+#3 @ 2999990,-1000000: 20x2000000
+#4 @ 5,5: 1x1",
+        );
+        // 1 and 2 share 5x2, 2 and 3 share 10x2, all three never meet
+        assert_eq!(fabric.overlap_area(), 30);
+        assert_eq!(fabric.clean_ids(), vec![4]);
+        assert_eq!(
+            fabric.overlap_graph().edges().collect::<Vec<_>>(),
+            vec![(1, 2, 10), (2, 3, 20)]
+        );
+    }
+}
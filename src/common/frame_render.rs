@@ -0,0 +1,387 @@
+use std::{
+    collections::HashMap,
+    env, fmt,
+    fs::File,
+    io::{self, BufWriter},
+    num::ParseIntError,
+    path::PathBuf,
+};
+
+use png::HasParameters;
+
+pub type Rgb = [u8; 3];
+
+/// Maps a simulation tile to the color it is drawn with.
+pub trait Palette {
+    fn color(&self) -> Rgb;
+}
+
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    cols: usize,
+    rows: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Canvas {
+    /// `tiles` are row major, `cols` has to divide their number
+    pub fn from_tiles<T: Palette>(tiles: &[T], cols: usize) -> Self {
+        assert!(
+            cols > 0 && tiles.len() % cols == 0,
+            "{} tiles can't be split into rows of {}",
+            tiles.len(),
+            cols
+        );
+        Canvas {
+            cols,
+            rows: tiles.len() / cols,
+            pixels: tiles.iter().map(Palette::color).collect(),
+        }
+    }
+
+    /// Overrides a single tile, used to draw entities that live outside of the tile grid
+    pub fn paint(&mut self, col: usize, row: usize, color: Rgb) {
+        self.pixels[row * self.cols + col] = color;
+    }
+
+    fn scaled_size(&self, scale: usize) -> (usize, usize) {
+        (self.cols * scale, self.rows * scale)
+    }
+
+    fn scaled_pixels(&self, scale: usize) -> impl Iterator<Item = Rgb> + '_ {
+        self.pixels.chunks(self.cols).flat_map(move |row| {
+            (0..scale).flat_map(move |_| {
+                row.iter()
+                    .flat_map(move |&p| std::iter::repeat(p).take(scale))
+            })
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Png(png::EncodingError),
+    EnvVariableIncorrect(&'static str, ParseIntError),
+    TooManyColors,
+    /// GIF frames are at most 65535 pixels on each side
+    TooLarge(usize, usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Png(e) => write!(f, "{}", e),
+            Error::EnvVariableIncorrect(var, e) => write!(f, "{} is not a number: {}", var, e),
+            Error::TooManyColors => write!(f, "more than 256 colors in a GIF frame"),
+            Error::TooLarge(width, height) => {
+                write!(f, "{}x{} frame is too large for a GIF", width, height)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<png::EncodingError> for Error {
+    fn from(e: png::EncodingError) -> Self {
+        Error::Png(e)
+    }
+}
+
+enum Output {
+    Disabled,
+    Pngs(PathBuf),
+    Gif(PathBuf, Option<gif::Encoder<BufWriter<File>>>),
+}
+
+/// Writes every nth frame of a simulation either as numbered PNGs or as a single animated GIF.
+pub struct Recorder {
+    output: Output,
+    every: usize,
+    scale: usize,
+    generation: usize,
+}
+
+impl Recorder {
+    pub fn disabled() -> Self {
+        Recorder {
+            output: Output::Disabled,
+            every: 1,
+            scale: 1,
+            generation: 0,
+        }
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        match self.output {
+            Output::Disabled => true,
+            _ => false,
+        }
+    }
+
+    /// `path` ending in `.gif` records an animation, any other path is used as the prefix for `<path>-<generation>.png` files
+    pub fn new(path: impl Into<PathBuf>, every: usize, scale: usize) -> Self {
+        let path = path.into();
+        let output = if path.extension().map_or(false, |e| e == "gif") {
+            Output::Gif(path, None)
+        } else {
+            Output::Pngs(path)
+        };
+        Recorder {
+            output,
+            every: every.max(1),
+            scale: scale.max(1),
+            generation: 0,
+        }
+    }
+
+    /// Reads the output path from `AOC_FRAMES`, the generation interval from `AOC_FRAMES_EVERY` and the pixels per tile from `AOC_FRAMES_SCALE`
+    pub fn from_env() -> Result<Self, Error> {
+        let var = |name| env::var(name).ok();
+        Recorder::from_vars(
+            var("AOC_FRAMES"),
+            var("AOC_FRAMES_EVERY"),
+            var("AOC_FRAMES_SCALE"),
+        )
+    }
+
+    fn from_vars(
+        path: Option<String>,
+        every: Option<String>,
+        scale: Option<String>,
+    ) -> Result<Self, Error> {
+        let path = match path {
+            Some(p) => p,
+            None => return Ok(Recorder::disabled()),
+        };
+        let every = number_or("AOC_FRAMES_EVERY", every, 1)?;
+        let scale = number_or("AOC_FRAMES_SCALE", scale, 4)?;
+        Ok(Recorder::new(path, every, scale))
+    }
+
+    /// The canvas is only drawn for the generations that are written out
+    pub fn record(&mut self, frame: impl FnOnce() -> Canvas) -> Result<(), Error> {
+        let generation = self.generation;
+        self.generation += 1;
+        if generation % self.every != 0 {
+            return Ok(());
+        }
+
+        match &mut self.output {
+            Output::Disabled => Ok(()),
+            Output::Pngs(prefix) => {
+                let mut path = prefix.clone().into_os_string();
+                path.push(format!("-{:06}.png", generation));
+                write_png(&frame(), self.scale, File::create(path)?)
+            }
+            Output::Gif(path, encoder) => {
+                let canvas = frame();
+                if encoder.is_none() {
+                    let (width, height) = gif_size(&canvas, self.scale)?;
+                    let file = BufWriter::new(File::create(path)?);
+                    let mut e = gif::Encoder::new(file, width, height, &[])?;
+                    e.write_extension(gif::ExtensionData::Repetitions(gif::Repeat::Infinite))?;
+                    *encoder = Some(e);
+                }
+                let encoder = encoder.as_mut().unwrap();
+                write_gif_frame(&canvas, self.scale, encoder)
+            }
+        }
+    }
+}
+
+fn number_or(var: &'static str, value: Option<String>, default: usize) -> Result<usize, Error> {
+    match value {
+        Some(v) => v.parse().map_err(|e| Error::EnvVariableIncorrect(var, e)),
+        None => Ok(default),
+    }
+}
+
+pub fn write_png(canvas: &Canvas, scale: usize, writer: impl io::Write) -> Result<(), Error> {
+    let (width, height) = canvas.scaled_size(scale);
+    let mut encoder = png::Encoder::new(writer, width as u32, height as u32);
+    encoder.set(png::ColorType::RGB).set(png::BitDepth::Eight);
+    let data: Vec<u8> = canvas
+        .scaled_pixels(scale)
+        .flat_map(|p| p.to_vec())
+        .collect();
+    encoder.write_header()?.write_image_data(&data)?;
+    Ok(())
+}
+
+fn gif_size(canvas: &Canvas, scale: usize) -> Result<(u16, u16), Error> {
+    let (width, height) = canvas.scaled_size(scale);
+    let max = usize::from(u16::max_value());
+    if width > max || height > max {
+        return Err(Error::TooLarge(width, height));
+    }
+    Ok((width as u16, height as u16))
+}
+
+fn write_gif_frame(
+    canvas: &Canvas,
+    scale: usize,
+    encoder: &mut gif::Encoder<impl io::Write>,
+) -> Result<(), Error> {
+    let (width, height) = gif_size(canvas, scale)?;
+    // tiles come from small enums so an exact local palette always fits
+    let mut palette: HashMap<Rgb, u8> = HashMap::new();
+    let mut indices = Vec::with_capacity(canvas.pixels.len() * scale * scale);
+    for p in canvas.scaled_pixels(scale) {
+        let index = match palette.get(&p) {
+            Some(&i) => i,
+            None if palette.len() == 256 => return Err(Error::TooManyColors),
+            None => {
+                let i = palette.len() as u8;
+                palette.insert(p, i);
+                i
+            }
+        };
+        indices.push(index);
+    }
+
+    let mut colors = vec![0; palette.len() * 3];
+    palette.iter().for_each(|(rgb, &i)| {
+        colors[i as usize * 3..i as usize * 3 + 3].copy_from_slice(rgb);
+    });
+
+    let mut frame = gif::Frame::from_palette_pixels(width, height, &indices, &colors, None);
+    frame.delay = 5;
+    encoder.write_frame(&frame).map_err(Error::Io)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy)]
+    enum Tile {
+        Dark,
+        Light,
+    }
+
+    impl Palette for Tile {
+        fn color(&self) -> Rgb {
+            match self {
+                Tile::Dark => [0, 0, 0],
+                Tile::Light => [0xff, 0xff, 0xff],
+            }
+        }
+    }
+
+    fn checkers() -> Canvas {
+        Canvas::from_tiles(&[Tile::Dark, Tile::Light, Tile::Light, Tile::Dark], 2)
+    }
+
+    #[test]
+    fn scaling() {
+        let mut canvas = checkers();
+        canvas.paint(1, 1, [1, 2, 3]);
+        assert_eq!(canvas.scaled_size(2), (4, 4));
+        let (d, l, p) = ([0, 0, 0], [0xff, 0xff, 0xff], [1, 2, 3]);
+        assert_eq!(
+            canvas.scaled_pixels(2).collect::<Vec<_>>(),
+            vec![d, d, l, l, d, d, l, l, l, l, p, p, l, l, p, p]
+        );
+        assert_eq!(canvas.scaled_pixels(1).count(), 4);
+    }
+
+    #[test]
+    fn png() {
+        let mut data = vec![];
+        write_png(&checkers(), 3, &mut data).unwrap();
+        let decoder = png::Decoder::new(&data[..]);
+        let (info, mut reader) = decoder.read_info().unwrap();
+        assert_eq!((info.width, info.height), (6, 6));
+        let mut pixels = vec![0; info.buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(&pixels[..6], &[0, 0, 0, 0, 0, 0]);
+        assert_eq!(&pixels[9..12], &[0xff, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn gif_palette() {
+        let mut data = vec![];
+        {
+            let mut encoder = gif::Encoder::new(&mut data, 2, 2, &[]).unwrap();
+            write_gif_frame(&checkers(), 1, &mut encoder).unwrap();
+        }
+        let mut decoder = gif::Decoder::new(&data[..]).read_info().unwrap();
+        let frame = decoder.read_next_frame().unwrap().unwrap().clone();
+        let palette = frame.palette.unwrap();
+        assert_eq!(palette, vec![0, 0, 0, 0xff, 0xff, 0xff]);
+        assert_eq!(frame.buffer.to_vec(), vec![0, 1, 1, 0]);
+
+        let mut colorful = Canvas::from_tiles(&[Tile::Dark; 257], 257);
+        (0..257).for_each(|i| colorful.paint(i, 0, [i as u8, (i / 256) as u8, 0]));
+        let mut encoder = gif::Encoder::new(vec![], 257, 1, &[]).unwrap();
+        match write_gif_frame(&colorful, 1, &mut encoder) {
+            Err(Error::TooManyColors) => {}
+            other => panic!("expected too many colors, got {:?}", other),
+        }
+
+        let wide = Canvas::from_tiles(&[Tile::Dark; 2], 2);
+        let mut encoder = gif::Encoder::new(vec![], 1, 1, &[]).unwrap();
+        match write_gif_frame(&wide, 40_000, &mut encoder) {
+            Err(Error::TooLarge(80_000, 40_000)) => {}
+            other => panic!("expected too large, got {:?}", other),
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn partial_row() {
+        Canvas::from_tiles(&[Tile::Dark; 3], 2);
+    }
+
+    #[test]
+    fn recorder_settings() {
+        let var = |value: &str| Some(value.to_string());
+        match Recorder::from_vars(var("frame"), var("three"), None) {
+            Err(Error::EnvVariableIncorrect("AOC_FRAMES_EVERY", _)) => {}
+            Err(e) => panic!("unexpected error {}", e),
+            Ok(_) => panic!("bad interval was accepted"),
+        }
+        let recorder = Recorder::from_vars(var("frame.gif"), None, var("2")).unwrap();
+        assert_eq!((recorder.every, recorder.scale), (1, 2));
+        match recorder.output {
+            Output::Gif(path, None) => assert_eq!(path, PathBuf::from("frame.gif")),
+            _ => panic!("expected a gif recorder"),
+        }
+        let disabled = Recorder::from_vars(None, var("three"), None).unwrap();
+        assert!(disabled.is_disabled());
+    }
+
+    #[test]
+    fn recorder_pngs() {
+        let dir = env::temp_dir().join(format!("frame_render_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut recorder = Recorder::new(dir.join("frame"), 3, 1);
+        let mut drawn = 0;
+        (0..7).for_each(|_| {
+            recorder
+                .record(|| {
+                    drawn += 1;
+                    checkers()
+                })
+                .unwrap()
+        });
+        assert_eq!(drawn, 3);
+        let mut written: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|f| f.unwrap().file_name().into_string().unwrap())
+            .collect();
+        written.sort();
+        assert_eq!(
+            written,
+            vec!["frame-000000.png", "frame-000003.png", "frame-000006.png"]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,844 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    fmt,
+    num::ParseIntError,
+    str::FromStr,
+};
+
+/// A guard's shift from the moment it begins until the next shift starts
+#[derive(Debug)]
+pub struct Schedule {
+    pub guard: u16,
+    pub start: Timestamp,
+    pub naps: Vec<Nap>,
+}
+
+/// Every guard's shifts keyed by guard ID
+pub type Guards = HashMap<u16, Vec<Schedule>>;
+
+/// Time asleep, `end` is the minute the guard wakes up and so it is not part of the nap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nap {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+impl Nap {
+    pub fn minutes(&self) -> impl Iterator<Item = Timestamp> {
+        (self.start.minutes_since_epoch()..self.end.minutes_since_epoch())
+            .map(Timestamp::from_minutes_since_epoch)
+    }
+
+    pub fn duration(&self) -> u32 {
+        (self.end.minutes_since_epoch() - self.start.minutes_since_epoch()) as u32
+    }
+}
+
+impl Schedule {
+    pub fn new(guard: u16, start: Timestamp) -> Self {
+        Schedule {
+            guard,
+            start,
+            naps: vec![],
+        }
+    }
+
+    /// Entries must already be sorted, every nap has to start and end within the same shift
+    pub fn guard_schedules(entries: &[Entry]) -> Result<HashMap<u16, Vec<Self>>, ScheduleError> {
+        let mut guards: HashMap<_, Vec<_>> = HashMap::new();
+        let mut schedule: Option<Schedule> = None;
+        let mut asleep: Option<Timestamp> = None;
+        let mut last: Option<Timestamp> = None;
+
+        for entry in entries {
+            let time = entry.timestamp();
+            if last.map_or(false, |l| time < l) {
+                return Err(ScheduleError::OutOfOrder(time));
+            }
+            last = Some(time);
+
+            match *entry {
+                Entry::BeginShift(_, id) => {
+                    if let Some(since) = asleep {
+                        return Err(ScheduleError::StillAsleep(since));
+                    }
+                    if let Some(s) = schedule.replace(Schedule::new(id, time)) {
+                        guards.entry(s.guard).or_default().push(s);
+                    }
+                }
+                Entry::FallingAsleep(_) => {
+                    if schedule.is_none() {
+                        return Err(ScheduleError::NoShift(time));
+                    }
+                    if asleep.is_some() {
+                        return Err(ScheduleError::AlreadyAsleep(time));
+                    }
+                    asleep = Some(time);
+                }
+                Entry::WakingUp(_) => {
+                    let s = schedule.as_mut().ok_or(ScheduleError::NoShift(time))?;
+                    let start = asleep.take().ok_or(ScheduleError::NotAsleep(time))?;
+                    s.naps.push(Nap { start, end: time });
+                }
+            }
+        }
+
+        if let Some(since) = asleep {
+            return Err(ScheduleError::StillAsleep(since));
+        }
+        if let Some(s) = schedule {
+            guards.entry(s.guard).or_default().push(s);
+        }
+        Ok(guards)
+    }
+
+    /// Minutes of the midnight hour (00:00 - 00:59) the guard spent asleep
+    pub fn asleep_minutes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.naps
+            .iter()
+            .flat_map(Nap::minutes)
+            .filter(|t| t.hour == 0)
+            .map(|t| usize::from(t.minute))
+    }
+
+    pub fn total_asleep(&self) -> u32 {
+        self.naps.iter().map(Nap::duration).sum()
+    }
+
+    /// One timeline for every midnight hour the guard slept in, in order. A shift without naps at
+    /// midnight gets the one it starts in, or the next one if it starts later in the day.
+    pub fn timelines(&self) -> Vec<Timeline> {
+        let mut nights: BTreeMap<_, _> = BTreeMap::new();
+        self.naps
+            .iter()
+            .flat_map(Nap::minutes)
+            .filter(|t| t.hour == 0)
+            .for_each(|t| {
+                nights
+                    .entry((t.year, t.month, t.day))
+                    .or_insert([false; 60])[usize::from(t.minute)] = true
+            });
+
+        if nights.is_empty() {
+            let wait = if self.start.hour == 0 { 0 } else { 24 * 60 };
+            let midnight =
+                Timestamp::from_minutes_since_epoch(self.start.minutes_since_epoch() + wait);
+            nights.insert((midnight.year, midnight.month, midnight.day), [false; 60]);
+        }
+        nights
+            .into_iter()
+            .map(|((year, month, day), asleep)| Timeline {
+                year,
+                month,
+                day,
+                guard: self.guard,
+                asleep,
+            })
+            .collect()
+    }
+}
+
+/// Sleep totals for a guard over all of its shifts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardStats {
+    pub guard: u16,
+    pub total_asleep: u32,
+    /// number of shifts in which the guard was asleep at each minute of the midnight hour
+    pub minutes: [u32; 60],
+}
+
+impl GuardStats {
+    pub fn new(guard: u16, schedules: &[Schedule]) -> Self {
+        let mut minutes = [0; 60];
+        schedules
+            .iter()
+            .flat_map(Schedule::asleep_minutes)
+            .for_each(|m| minutes[m] += 1);
+        GuardStats {
+            guard,
+            total_asleep: schedules.iter().map(Schedule::total_asleep).sum(),
+            minutes,
+        }
+    }
+
+    /// Stats for every guard sorted by guard ID
+    pub fn all(guards: &Guards) -> Vec<Self> {
+        let mut stats: Vec<_> = guards
+            .iter()
+            .map(|(&id, schedules)| GuardStats::new(id, schedules))
+            .collect();
+        stats.sort_unstable_by_key(|s| s.guard);
+        stats
+    }
+
+    /// Every minute tied for the most shifts asleep and that number of shifts, no minutes if the
+    /// guard never slept during the midnight hour
+    pub fn sleepiest_minutes(&self) -> (Vec<usize>, u32) {
+        let max = self.minutes.iter().cloned().max().unwrap_or(0);
+        if max == 0 {
+            return (vec![], 0);
+        }
+        let minutes = (0..60).filter(|&m| self.minutes[m] == max).collect();
+        (minutes, max)
+    }
+}
+
+/// What a guard did during the midnight hour of a single day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timeline {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub guard: u16,
+    pub asleep: [bool; 60],
+}
+
+/// Every shift drawn as in the puzzle, `#` when asleep and `.` when awake. Dates include the
+/// year only when the shifts span more than one.
+pub struct Report {
+    timelines: Vec<Timeline>,
+}
+
+impl Report {
+    pub fn new(guards: &Guards) -> Self {
+        let mut timelines: Vec<_> = guards
+            .values()
+            .flat_map(|schedules| schedules.iter().flat_map(Schedule::timelines))
+            .collect();
+        timelines.sort_unstable_by_key(|t| (t.year, t.month, t.day, t.guard));
+        Report { timelines }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let id_width = self
+            .timelines
+            .iter()
+            .map(|t| format!("#{}", t.guard).len())
+            .max()
+            .unwrap_or(2);
+        let show_year = match (self.timelines.first(), self.timelines.last()) {
+            (Some(first), Some(last)) => first.year != last.year,
+            _ => false,
+        };
+        let date = |t: &Timeline| {
+            if show_year {
+                format!("{:04}-{:02}-{:02}", t.year, t.month, t.day)
+            } else {
+                format!("{:02}-{:02}", t.month, t.day)
+            }
+        };
+        let date_width = if show_year { 10 } else { 5 };
+
+        writeln!(
+            f,
+            "{:date_width$}  {:id_width$}  Minute",
+            "Date",
+            "ID",
+            date_width = date_width,
+            id_width = id_width
+        )?;
+        let tens: String = (0..60).map(|m| (b'0' + m / 10) as char).collect();
+        let ones: String = (0..60).map(|m| (b'0' + m % 10) as char).collect();
+        let indent = date_width + id_width + 4;
+        writeln!(f, "{:width$}{}", "", tens, width = indent)?;
+        writeln!(f, "{:width$}{}", "", ones, width = indent)?;
+
+        self.timelines.iter().try_for_each(|t| {
+            let minutes: String = t
+                .asleep
+                .iter()
+                .map(|&a| if a { '#' } else { '.' })
+                .collect();
+            let id = format!("#{}", t.guard);
+            writeln!(
+                f,
+                "{}  {:width$}  {}",
+                date(t),
+                id,
+                minutes,
+                width = id_width
+            )
+        })
+    }
+}
+
+/// Where an entry was read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Origin {
+    pub source: String,
+    pub line: usize,
+}
+
+/// Entries gathered from any number of logs, in any order
+#[derive(Debug, Default)]
+pub struct GuardLog {
+    entries: Vec<(Entry, Origin)>,
+    errors: Vec<LogError>,
+}
+
+impl GuardLog {
+    pub fn new() -> Self {
+        GuardLog::default()
+    }
+
+    /// Blank lines are skipped, lines that fail to parse are kept as errors for `schedules`
+    pub fn add(&mut self, source: &str, log: &str) {
+        log.lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .for_each(|(i, l)| {
+                let origin = Origin {
+                    source: source.to_string(),
+                    line: i + 1,
+                };
+                match Entry::try_from(l.trim()) {
+                    Ok(entry) => self.entries.push((entry, origin)),
+                    Err(e) => self.errors.push(LogError::Parse(origin, e)),
+                }
+            });
+    }
+
+    /// Every entry once in chronological order, entries logged at the same minute keep the order
+    /// they were added in. Identical entries after the first and sleep events before the first
+    /// shift are dropped with a warning.
+    pub fn merged(&self) -> (Vec<Entry>, Vec<LogWarning>) {
+        let mut sorted: Vec<_> = self.entries.iter().collect();
+        sorted.sort_by_key(|(entry, _)| entry.timestamp());
+
+        let mut warnings = vec![];
+        let mut merged: Vec<Entry> = vec![];
+        for (i, (entry, origin)) in sorted.iter().enumerate() {
+            let same_minute = sorted[..i]
+                .iter()
+                .rev()
+                .take_while(|(e, _)| e.timestamp() == entry.timestamp());
+            if let Some((_, first)) = same_minute.clone().find(|(e, _)| e == entry) {
+                warnings.push(LogWarning::Duplicate((*origin).clone(), first.clone()));
+            } else if merged.is_empty() && entry.guard().is_none() {
+                warnings.push(LogWarning::Orphan((*origin).clone(), *entry));
+            } else {
+                merged.push(*entry);
+            }
+        }
+        (merged, warnings)
+    }
+
+    /// Schedules of every guard from the merged log. Nothing is built if any line failed to
+    /// parse, all of them are reported instead.
+    pub fn schedules(&self) -> Result<(Guards, Vec<LogWarning>), Vec<LogError>> {
+        if !self.errors.is_empty() {
+            return Err(self.errors.clone());
+        }
+        let (entries, warnings) = self.merged();
+        let guards =
+            Schedule::guard_schedules(&entries).map_err(|e| vec![LogError::Schedule(e)])?;
+        Ok((guards, warnings))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogWarning {
+    /// an entry identical to the one at the second origin
+    Duplicate(Origin, Origin),
+    /// a sleep event with no guard on shift yet
+    Orphan(Origin, Entry),
+}
+
+impl fmt::Display for LogWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogWarning::Duplicate(o, first) => write!(
+                f,
+                "{}:{}: duplicate of {}:{}, ignored",
+                o.source, o.line, first.source, first.line
+            ),
+            LogWarning::Orphan(o, entry) => write!(
+                f,
+                "{}:{}: [{}] no guard on shift yet, ignored",
+                o.source,
+                o.line,
+                entry.timestamp()
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogError {
+    Parse(Origin, EntryParseError),
+    Schedule(ScheduleError),
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogError::Parse(o, e) => write!(f, "{}:{}: {}", o.source, o.line, e),
+            LogError::Schedule(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl Timestamp {
+    pub fn minutes_since_epoch(&self) -> i64 {
+        let days = days_from_civil(
+            i64::from(self.year),
+            i64::from(self.month),
+            i64::from(self.day),
+        );
+        days * 24 * 60 + i64::from(self.hour) * 60 + i64::from(self.minute)
+    }
+
+    pub fn from_minutes_since_epoch(minutes: i64) -> Self {
+        let (year, month, day) = civil_from_days(minutes.div_euclid(24 * 60));
+        let minutes = minutes.rem_euclid(24 * 60);
+        Timestamp {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            hour: (minutes / 60) as u8,
+            minute: (minutes % 60) as u8,
+        }
+    }
+}
+
+fn is_leap(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+impl FromStr for Timestamp {
+    type Err = EntryParseError;
+
+    /// Parses `YYYY-MM-DD hh:mm`
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let invalid = || EntryParseError::InvalidTimestamp(raw.to_string());
+        let mut parts = raw.split(&['-', ' ', ':'][..]);
+        let mut next = || -> Result<u16, EntryParseError> {
+            parts
+                .next()
+                .ok_or_else(invalid)?
+                .parse()
+                .map_err(|_| invalid())
+        };
+        let (year, month, day, hour, minute) = (next()?, next()?, next()?, next()?, next()?);
+        if parts.next().is_some()
+            || !(1..=12).contains(&month)
+            || day < 1
+            || i64::from(day) > days_in_month(i64::from(year), i64::from(month))
+            || hour > 23
+            || minute > 59
+        {
+            return Err(invalid());
+        }
+
+        Ok(Timestamp {
+            year,
+            month: month as u8,
+            day: day as u8,
+            hour: hour as u8,
+            minute: minute as u8,
+        })
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute
+        )
+    }
+}
+
+impl TryFrom<&str> for Entry {
+    type Error = EntryParseError;
+
+    fn try_from(raw: &str) -> Result<Self, Self::Error> {
+        let close = raw.find(']').filter(|_| raw.starts_with('['));
+        let close = close.ok_or_else(|| EntryParseError::MissingTimestamp(raw.to_string()))?;
+        let date = raw[1..close].parse()?;
+        let action = raw[close + 1..].trim();
+        if action == "falls asleep" {
+            Ok(Entry::FallingAsleep(date))
+        } else if action == "wakes up" {
+            Ok(Entry::WakingUp(date))
+        } else if action.starts_with("Guard #") && action.ends_with(" begins shift") {
+            let id = action["Guard #".len()..action.len() - " begins shift".len()].parse()?;
+            Ok(Entry::BeginShift(date, id))
+        } else {
+            Err(EntryParseError::UnknownAction(action.to_string()))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryParseError {
+    MissingTimestamp(String),
+    InvalidTimestamp(String),
+    UnknownAction(String),
+    InvalidGuardId(ParseIntError),
+}
+
+impl From<ParseIntError> for EntryParseError {
+    fn from(e: ParseIntError) -> Self {
+        EntryParseError::InvalidGuardId(e)
+    }
+}
+
+impl fmt::Display for EntryParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EntryParseError::MissingTimestamp(l) => write!(f, "no '[timestamp]' in '{}'", l),
+            EntryParseError::InvalidTimestamp(t) => write!(f, "invalid timestamp '{}'", t),
+            EntryParseError::UnknownAction(a) => write!(f, "unknown action '{}'", a),
+            EntryParseError::InvalidGuardId(e) => write!(f, "invalid guard id: {}", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    OutOfOrder(Timestamp),
+    NoShift(Timestamp),
+    AlreadyAsleep(Timestamp),
+    NotAsleep(Timestamp),
+    /// the shift ended, or the log did, with the guard still asleep since the given time
+    StillAsleep(Timestamp),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScheduleError::OutOfOrder(t) => write!(f, "[{}] entry is out of order", t),
+            ScheduleError::NoShift(t) => write!(f, "[{}] no guard is on shift", t),
+            ScheduleError::AlreadyAsleep(t) => write!(f, "[{}] guard is already asleep", t),
+            ScheduleError::NotAsleep(t) => write!(f, "[{}] guard is not asleep", t),
+            ScheduleError::StillAsleep(t) => {
+                write!(f, "[{}] guard fell asleep and never woke up", t)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Entry {
+    BeginShift(Timestamp, u16),
+    FallingAsleep(Timestamp),
+    WakingUp(Timestamp),
+}
+
+impl Entry {
+    pub fn timestamp(&self) -> Timestamp {
+        match *self {
+            Entry::BeginShift(t, _) => t,
+            Entry::FallingAsleep(t) => t,
+            Entry::WakingUp(t) => t,
+        }
+    }
+
+    /// The guard beginning a shift, `None` for sleep events
+    pub fn guard(&self) -> Option<u16> {
+        match *self {
+            Entry::BeginShift(_, id) => Some(id),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(input: &str) -> Vec<Entry> {
+        input.lines().map(|l| Entry::try_from(l).unwrap()).collect()
+    }
+
+    const SAMPLE: &str = r"[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up";
+
+    #[test]
+    fn stats() {
+        let guards = Schedule::guard_schedules(&entries(SAMPLE)).unwrap();
+        let stats = GuardStats::all(&guards);
+        assert_eq!(
+            stats
+                .iter()
+                .map(|s| (s.guard, s.total_asleep))
+                .collect::<Vec<_>>(),
+            vec![(10, 50), (99, 30)]
+        );
+        assert_eq!(stats[0].sleepiest_minutes(), (vec![24], 2));
+        assert_eq!(stats[1].sleepiest_minutes(), (vec![45], 3));
+        assert_eq!(stats[1].minutes[40..47], [2, 2, 2, 2, 2, 3, 2]);
+
+        let mut no_sleep = stats[0].clone();
+        no_sleep.minutes = [0; 60];
+        no_sleep.minutes[3] = 1;
+        no_sleep.minutes[7] = 1;
+        assert_eq!(no_sleep.sleepiest_minutes(), (vec![3, 7], 1));
+    }
+
+    #[test]
+    fn report() {
+        let guards = Schedule::guard_schedules(&entries(SAMPLE)).unwrap();
+        assert_eq!(
+            Report::new(&guards).to_string(),
+            r"Date   ID   Minute
+            000000000011111111112222222222333333333344444444445555555555
+            012345678901234567890123456789012345678901234567890123456789
+11-01  #10  .....####################.....#########################.....
+11-02  #99  ........................................##########..........
+11-03  #10  ........................#####...............................
+11-04  #99  ....................................##########..............
+11-05  #99  .............................................##########.....
+"
+        );
+    }
+
+    #[test]
+    fn report_dates() {
+        let guards = Schedule::guard_schedules(&entries(
+            r"[1518-12-31 23:50] Guard #10 begins shift
+[1519-01-01 00:10] falls asleep
+[1519-01-01 00:20] wakes up
+[1519-11-01 00:00] Guard #10 begins shift
+[1519-11-01 01:30] Guard #99 begins shift",
+        ))
+        .unwrap();
+        let timelines: Vec<_> = Report::new(&guards)
+            .timelines
+            .iter()
+            .map(|t| (t.year, t.month, t.day, t.guard))
+            .collect();
+        assert_eq!(
+            timelines,
+            vec![(1519, 1, 1, 10), (1519, 11, 1, 10), (1519, 11, 2, 99)]
+        );
+
+        let guards = Schedule::guard_schedules(&entries(
+            r"[1518-11-01 00:00] Guard #10 begins shift
+[1519-11-01 00:00] Guard #10 begins shift",
+        ))
+        .unwrap();
+        let report = Report::new(&guards).to_string();
+        let rows: Vec<_> = report.lines().map(|l| &l[..17]).collect();
+        assert_eq!(
+            rows,
+            vec![
+                "Date        ID   ",
+                "                 ",
+                "                 ",
+                "1518-11-01  #10  ",
+                "1519-11-01  #10  ",
+            ]
+        );
+    }
+
+    #[test]
+    fn naps_across_midnight() {
+        let entries = entries(
+            r"[1518-02-28 22:58] Guard #7 begins shift
+[1518-02-28 23:58] falls asleep
+[1518-03-01 00:02] wakes up",
+        );
+        let guards = Schedule::guard_schedules(&entries).unwrap();
+        let schedule = &guards[&7][0];
+        assert_eq!(schedule.total_asleep(), 4);
+        assert_eq!(schedule.asleep_minutes().collect::<Vec<_>>(), vec![0, 1]);
+        let timelines = schedule.timelines();
+        assert_eq!(
+            (timelines.len(), timelines[0].month, timelines[0].day),
+            (1, 3, 1)
+        );
+        assert_eq!(timelines[0].asleep[..3], [true, true, false]);
+    }
+
+    #[test]
+    fn shift_across_two_midnights() {
+        let two_nights = entries(
+            r"[1518-03-01 22:00] Guard #7 begins shift
+[1518-03-02 00:10] falls asleep
+[1518-03-02 00:11] wakes up
+[1518-03-03 00:20] falls asleep
+[1518-03-03 00:22] wakes up",
+        );
+        let guards = Schedule::guard_schedules(&two_nights).unwrap();
+        let timelines = guards[&7][0].timelines();
+        let asleep = |t: &Timeline| (0..60).filter(|&m| t.asleep[m]).collect::<Vec<_>>();
+        assert_eq!(
+            timelines
+                .iter()
+                .map(|t| (t.day, asleep(t)))
+                .collect::<Vec<_>>(),
+            vec![(2, vec![10]), (3, vec![20, 21])]
+        );
+    }
+
+    #[test]
+    fn invalid_logs() {
+        assert_eq!(
+            Entry::try_from("[1518-02-29 00:00] wakes up"),
+            Err(EntryParseError::InvalidTimestamp(
+                "1518-02-29 00:00".to_string()
+            ))
+        );
+        assert_eq!(
+            Entry::try_from("[1518-02-20 00:00] sings"),
+            Err(EntryParseError::UnknownAction("sings".to_string()))
+        );
+
+        let time = "1518-11-01 00:05".parse().unwrap();
+        let orphan = entries("[1518-11-01 00:05] falls asleep");
+        assert_eq!(
+            Schedule::guard_schedules(&orphan).unwrap_err(),
+            ScheduleError::NoShift(time)
+        );
+
+        let twice = entries(
+            r"[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:06] falls asleep",
+        );
+        assert_eq!(
+            Schedule::guard_schedules(&twice).unwrap_err(),
+            ScheduleError::AlreadyAsleep("1518-11-01 00:06".parse().unwrap())
+        );
+
+        let never_wakes = entries(
+            r"[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-02 00:00] Guard #11 begins shift",
+        );
+        assert_eq!(
+            Schedule::guard_schedules(&never_wakes).unwrap_err(),
+            ScheduleError::StillAsleep(time)
+        );
+    }
+
+    #[test]
+    fn merged_logs() {
+        let lines: Vec<_> = SAMPLE.lines().collect();
+        let first = format!("[1518-10-31 23:50] wakes up\n{}", lines[..8].join("\n"));
+        let second: Vec<_> = lines[5..].iter().rev().cloned().collect();
+
+        let mut log = GuardLog::new();
+        log.add("a", &first);
+        log.add("b", &second.join("\n"));
+        let (merged, warnings) = log.merged();
+        assert_eq!(merged, entries(SAMPLE));
+
+        let origin = |source: &str, line| Origin {
+            source: source.to_string(),
+            line,
+        };
+        assert_eq!(
+            warnings,
+            vec![
+                LogWarning::Orphan(
+                    origin("a", 1),
+                    Entry::WakingUp("1518-10-31 23:50".parse().unwrap())
+                ),
+                LogWarning::Duplicate(origin("b", 12), origin("a", 7)),
+                LogWarning::Duplicate(origin("b", 11), origin("a", 8)),
+                LogWarning::Duplicate(origin("b", 10), origin("a", 9)),
+            ]
+        );
+
+        let (guards, _) = log.schedules().unwrap();
+        assert_eq!(
+            GuardStats::all(&guards),
+            GuardStats::all(&{ Schedule::guard_schedules(&entries(SAMPLE)).unwrap() })
+        );
+    }
+
+    #[test]
+    fn inconsistent_logs() {
+        let mut log = GuardLog::new();
+        log.add("a", "[1518-11-01 00:00] Guard #10 begins shift\n\ngarbage");
+        log.add("b", "[1518-11-01 00:05] Guard #x begins shift");
+        let errors = log.schedules().unwrap_err();
+        assert_eq!(
+            errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+            vec![
+                "a:3: no '[timestamp]' in 'garbage'",
+                "b:1: invalid guard id: invalid digit found in string",
+            ]
+        );
+
+        let mut log = GuardLog::new();
+        log.add(
+            "a",
+            "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] wakes up",
+        );
+        assert_eq!(
+            log.schedules().unwrap_err(),
+            vec![LogError::Schedule(ScheduleError::NotAsleep(
+                "1518-11-01 00:05".parse().unwrap()
+            ))]
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// How many times each user-perceived character (grapheme) appears in an ID
+pub fn histogram(id: &str) -> BTreeMap<&str, usize> {
+    id.graphemes(true).fold(BTreeMap::new(), |mut counts, g| {
+        *counts.entry(g).or_default() += 1;
+        counts
+    })
+}
+
+/// For every requested repetition, the number of IDs that contain some grapheme exactly that many times.
+/// An ID counts at most once per repetition.
+pub fn repetition_counts<'a>(
+    ids: impl IntoIterator<Item = &'a str>,
+    repetitions: &[usize],
+) -> Vec<usize> {
+    ids.into_iter()
+        .map(|id| histogram(id).values().cloned().collect::<BTreeSet<_>>())
+        .fold(vec![0; repetitions.len()], |mut totals, repeated| {
+            repetitions
+                .iter()
+                .zip(totals.iter_mut())
+                .filter(|(r, _)| repeated.contains(r))
+                .for_each(|(_, total)| *total += 1);
+            totals
+        })
+}
+
+/// Combines the counts from `repetition_counts`, given in the same order as `repetitions`
+pub fn checksum<'a, T>(
+    ids: impl IntoIterator<Item = &'a str>,
+    repetitions: &[usize],
+    combine: impl FnOnce(&[usize]) -> T,
+) -> T {
+    combine(&repetition_counts(ids, repetitions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDS: &[&str] = &[
+        "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab",
+    ];
+
+    #[test]
+    fn counts() {
+        assert_eq!(
+            repetition_counts(IDS.iter().cloned(), &[1, 2, 3, 4]),
+            vec![6, 4, 3, 0]
+        );
+        assert_eq!(
+            checksum(IDS.iter().cloned(), &[2, 3], |c| c.iter().sum::<usize>()),
+            7
+        );
+    }
+
+    #[test]
+    fn graphemes() {
+        let id = "e\u{301}e\u{301}xa🇫🇷🇫🇷";
+        let counts = histogram(id);
+        assert_eq!(counts["e\u{301}"], 2);
+        assert_eq!(counts["🇫🇷"], 2);
+        assert_eq!(counts.get("e"), None);
+        assert_eq!(repetition_counts(vec![id], &[1, 2]), vec![1, 1]);
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+
+/// The letters the messages are drawn with, 6 columns by 10 rows, plus the smaller H and I
+/// of the puzzle's example. Rows are separated by spaces.
+const FONT: &[(char, &str)] = &[
+    (
+        'A',
+        "..##.. .#..#. #....# #....# #....# ###### #....# #....# #....# #....#",
+    ),
+    (
+        'B',
+        "#####. #....# #....# #....# #####. #....# #....# #....# #....# #####.",
+    ),
+    (
+        'C',
+        ".####. #....# #..... #..... #..... #..... #..... #..... #....# .####.",
+    ),
+    (
+        'E',
+        "###### #..... #..... #..... #####. #..... #..... #..... #..... ######",
+    ),
+    (
+        'F',
+        "###### #..... #..... #..... #####. #..... #..... #..... #..... #.....",
+    ),
+    (
+        'G',
+        ".####. #....# #..... #..... #..... #..### #....# #....# #...## .###.#",
+    ),
+    (
+        'H',
+        "#....# #....# #....# #....# ###### #....# #....# #....# #....# #....#",
+    ),
+    (
+        'J',
+        "...### ....#. ....#. ....#. ....#. ....#. ....#. #...#. #...#. .###..",
+    ),
+    (
+        'K',
+        "#....# #...#. #..#.. #.#... ##.... ##.... #.#... #..#.. #...#. #....#",
+    ),
+    (
+        'L',
+        "#..... #..... #..... #..... #..... #..... #..... #..... #..... ######",
+    ),
+    (
+        'N',
+        "#....# ##...# ##...# #.#..# #.#..# #..#.# #..#.# #...## #...## #....#",
+    ),
+    (
+        'P',
+        "#####. #....# #....# #....# #####. #..... #..... #..... #..... #.....",
+    ),
+    (
+        'R',
+        "#####. #....# #....# #....# #####. #..#.. #...#. #...#. #....# #....#",
+    ),
+    (
+        'X',
+        "#....# #....# .#..#. .#..#. ..##.. ..##.. .#..#. .#..#. #....# #....#",
+    ),
+    (
+        'Z',
+        "###### .....# .....# ....#. ...#.. ..#... .#.... #..... #..... ######",
+    ),
+    ('H', "#...# #...# #...# ##### #...# #...# #...# #...#"),
+    ('I', "### .#. .#. .#. .#. .#. .#. ###"),
+];
+
+/// A group of lit columns that matches no letter of the font
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glyph {
+    /// first column of the glyph in the rendering
+    pub column: usize,
+    pub rows: Vec<String>,
+}
+
+/// The message could only be read partially, unrecognised letters are `?` in `text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OcrError {
+    pub text: String,
+    pub unknown: Vec<Glyph>,
+}
+
+/// Reads a message drawn with `#` for lit pixels, one line per row. Letters are the groups of
+/// columns with at least one lit pixel, so they need an unlit column between them.
+pub fn read(rendering: &str) -> Result<String, OcrError> {
+    let rows: Vec<&[u8]> = rendering.lines().map(str::as_bytes).collect();
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let lit = |x: usize, y: usize| rows[y].get(x) == Some(&b'#');
+    let lit_column = |x: usize| (0..rows.len()).any(|y| lit(x, y));
+
+    let mut text = String::new();
+    let mut unknown = vec![];
+    let mut x = 0;
+    while x < width {
+        if !lit_column(x) {
+            x += 1;
+            continue;
+        }
+        let column = x;
+        while x < width && lit_column(x) {
+            x += 1;
+        }
+
+        let lit_row = |y: &usize| (column..x).any(|x| lit(x, *y));
+        let top = (0..rows.len()).find(lit_row).unwrap();
+        let bottom = (0..rows.len()).rev().find(lit_row).unwrap();
+        let glyph: Vec<String> = (top..=bottom)
+            .map(|y| {
+                (column..x)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        let letter = FONT
+            .iter()
+            .find(|(_, pixels)| *pixels == glyph.join(" "))
+            .map(|&(letter, _)| letter);
+        match letter {
+            Some(letter) => text.push(letter),
+            None => {
+                text.push('?');
+                unknown.push(Glyph {
+                    column,
+                    rows: glyph,
+                });
+            }
+        }
+    }
+
+    if unknown.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError { text, unknown })
+    }
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unrecognised letters in '{}'", self.text)?;
+        self.unknown.iter().try_for_each(|glyph| {
+            write!(f, "\ncolumn {}:", glyph.column)?;
+            glyph.rows.iter().try_for_each(|row| write!(f, "\n{}", row))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample() {
+        let rendering = r"#...#..###
+#...#...#.
+#...#...#.
+#####...#.
+#...#...#.
+#...#...#.
+#...#...#.
+#...#..###
+";
+        assert_eq!(read(rendering), Ok("HI".to_string()));
+        assert_eq!(read(""), Ok(String::new()));
+    }
+
+    #[test]
+    fn full_font() {
+        let letters: Vec<Vec<_>> = FONT
+            .iter()
+            .map(|(_, pixels)| pixels.split(' ').collect())
+            .filter(|rows: &Vec<_>| rows.len() == 10)
+            .collect();
+        let rendering: Vec<String> = (0..10)
+            .map(|y| {
+                let row: Vec<_> = letters.iter().map(|rows| rows[y]).collect();
+                format!("..{}..", row.join(".."))
+            })
+            .collect();
+        assert_eq!(
+            read(&rendering.join("\n")),
+            Ok("ABCEFGHJKLNPRXZ".to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_glyphs() {
+        let rendering = "#...#..#.#\n#####..###\n#...#...#.\n";
+        let error = read(rendering).unwrap_err();
+        assert_eq!(error.text, "??");
+        assert_eq!(
+            error.unknown[1],
+            Glyph {
+                column: 7,
+                rows: vec!["#.#".to_string(), "###".to_string(), ".#.".to_string()],
+            }
+        );
+        assert!(error
+            .to_string()
+            .starts_with("unrecognised letters in '??'\ncolumn 0:\n#...#\n#####\n"));
+    }
+}
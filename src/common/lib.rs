@@ -1,5 +1,22 @@
+pub mod box_index;
+pub mod calibration;
+pub mod circle;
+pub mod fabric;
+pub mod frame_render;
+pub mod guard_schedule;
 pub mod input_fetcher;
+pub mod letter_checksum;
+pub mod letter_ocr;
+pub mod license_tree;
+pub mod marble_game;
+pub mod polymer;
+pub mod safe_region;
 pub mod sanitize;
+pub mod star_alignment;
+pub mod task_graph;
+#[cfg(test)]
+mod test_rng;
+pub mod voronoi;
 #[macro_use]
 pub mod macros;
 
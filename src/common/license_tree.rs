@@ -0,0 +1,505 @@
+use std::{fmt, str::FromStr};
+
+/// A node of the license file: its children followed by its metadata entries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseNode {
+    pub children: Vec<LicenseNode>,
+    pub metadata: Vec<usize>,
+}
+
+/// Size of a tree, a lone node has a depth of 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeStats {
+    pub nodes: usize,
+    pub depth: usize,
+    pub metadata_entries: usize,
+}
+
+impl LicenseNode {
+    pub fn leaf(metadata: Vec<usize>) -> Self {
+        LicenseNode {
+            children: vec![],
+            metadata,
+        }
+    }
+
+    /// Visits the tree bottom up, `visit` gets every node along with what it returned for each
+    /// of the node's children
+    pub fn fold<T>(&self, visit: &mut impl FnMut(&LicenseNode, Vec<T>) -> T) -> T {
+        // every node on the way down along with the results of the children visited so far
+        let mut stack = vec![(self, vec![])];
+        loop {
+            let (node, done) = stack.last().unwrap();
+            if let Some(child) = node.children.get(done.len()) {
+                stack.push((child, vec![]));
+                continue;
+            }
+
+            let (node, children) = stack.pop().unwrap();
+            let result = visit(node, children);
+            match stack.last_mut() {
+                Some((_, siblings)) => siblings.push(result),
+                None => return result,
+            }
+        }
+    }
+
+    /// Part one: every metadata entry in the tree added up
+    pub fn metadata_sum(&self) -> usize {
+        self.fold(&mut |node, children: Vec<usize>| {
+            node.metadata.iter().sum::<usize>() + children.iter().sum::<usize>()
+        })
+    }
+
+    /// Part two: the metadata sum of a leaf, otherwise the values of the children its metadata
+    /// entries point to, 1-based. Entries pointing to no child count for nothing.
+    pub fn value(&self) -> usize {
+        self.fold(&mut |node, children: Vec<usize>| node.value_from(&children))
+    }
+
+    /// `value` given the values of the children
+    fn value_from(&self, children: &[usize]) -> usize {
+        if children.is_empty() {
+            self.metadata.iter().sum()
+        } else {
+            self.metadata
+                .iter()
+                .filter_map(|&m| m.checked_sub(1))
+                .filter_map(|i| children.get(i))
+                .sum()
+        }
+    }
+
+    /// `value` of every node in the order `nodes` lists them. Children come after their parent
+    /// there, so going backwards every node finds the values of its children ready.
+    fn values(&self) -> Vec<usize> {
+        let mut order: Vec<(&LicenseNode, Vec<usize>)> = vec![];
+        let mut pending: Vec<(Option<usize>, _)> = vec![(None, self)];
+        while let Some((parent, node)) = pending.pop() {
+            let index = order.len();
+            if let Some(parent) = parent {
+                order[parent].1.push(index);
+            }
+            pending.extend(node.children.iter().rev().map(|c| (Some(index), c)));
+            order.push((node, vec![]));
+        }
+
+        let mut values = vec![0; order.len()];
+        order
+            .iter()
+            .enumerate()
+            .rev()
+            .for_each(|(index, (node, children))| {
+                let children: Vec<_> = children.iter().map(|&c| values[c]).collect();
+                values[index] = node.value_from(&children);
+            });
+        values
+    }
+
+    pub fn stats(&self) -> TreeStats {
+        self.fold(&mut |node, children: Vec<TreeStats>| TreeStats {
+            nodes: 1 + children.iter().map(|c| c.nodes).sum::<usize>(),
+            depth: 1 + children.iter().map(|c| c.depth).max().unwrap_or(0),
+            metadata_entries: node.metadata.len()
+                + children.iter().map(|c| c.metadata_entries).sum::<usize>(),
+        })
+    }
+
+    /// Every node with its label and path, in the order they appear in the input
+    pub fn nodes(&self) -> Vec<(String, Vec<usize>, &LicenseNode)> {
+        let mut nodes = vec![];
+        let mut pending = vec![(vec![], self)];
+        while let Some((path, node)) = pending.pop() {
+            let children = node.children.iter().enumerate().rev();
+            pending.extend(children.map(|(i, child)| {
+                let mut path = path.clone();
+                path.push(i + 1);
+                (path, child)
+            }));
+            nodes.push((label(nodes.len()), path, node));
+        }
+        nodes
+    }
+
+    /// The node reached by following 1-based child indices from this one
+    pub fn node_at(&self, path: &[usize]) -> Option<&LicenseNode> {
+        path.iter().try_fold(self, |node, &i| {
+            i.checked_sub(1).and_then(|i| node.children.get(i))
+        })
+    }
+
+    /// Metadata entries that `value` ignores because they point to no child
+    pub fn dangling_references(&self) -> Vec<DanglingReference> {
+        self.nodes()
+            .into_iter()
+            .filter(|(_, _, node)| !node.children.is_empty())
+            .flat_map(|(label, path, node)| {
+                let children = node.children.len();
+                node.metadata
+                    .iter()
+                    .filter(move |&&entry| entry == 0 || entry > children)
+                    .map(move |&entry| DanglingReference {
+                        label: label.clone(),
+                        path: path.clone(),
+                        entry,
+                        children,
+                    })
+            })
+            .collect()
+    }
+
+    pub fn outline(&self) -> Outline<'_> {
+        Outline { root: self }
+    }
+
+    /// The flat format the tree was parsed from
+    pub fn to_numbers(&self) -> Vec<usize> {
+        let mut numbers = vec![self.children.len(), self.metadata.len()];
+        // nodes whose header is written, with the index of the next child to write
+        let mut stack = vec![(self, 0)];
+        while let Some((node, next)) = stack.pop() {
+            match node.children.get(next) {
+                Some(child) => {
+                    stack.push((node, next + 1));
+                    numbers.extend(&[child.children.len(), child.metadata.len()]);
+                    stack.push((child, 0));
+                }
+                None => numbers.extend(&node.metadata),
+            }
+        }
+        numbers
+    }
+}
+
+impl Drop for LicenseNode {
+    /// Detaches the descendants one by one, dropping them recursively could overflow the stack
+    /// on a deep tree just like parsing it
+    fn drop(&mut self) {
+        let mut pending = vec![];
+        pending.append(&mut self.children);
+        while let Some(mut node) = pending.pop() {
+            pending.append(&mut node.children);
+        }
+    }
+}
+
+/// A metadata entry of a node with children that does not point to any of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DanglingReference {
+    pub label: String,
+    /// 1-based child indices from the root to the node
+    pub path: Vec<usize>,
+    pub entry: usize,
+    pub children: usize,
+}
+
+/// Every node on its own line, labelled A, B, C... in the order they appear in the input and
+/// indented by depth
+pub struct Outline<'a> {
+    root: &'a LicenseNode,
+}
+
+/// A node whose header has been read but not all of its children
+struct Partial {
+    missing_children: usize,
+    metadata: usize,
+    children: Vec<LicenseNode>,
+}
+
+/// A, B, ..., Z, AA, AB, ...
+fn label(index: usize) -> String {
+    let mut label = vec![];
+    let mut index = index + 1;
+    while index > 0 {
+        index -= 1;
+        label.push((b'A' + (index % 26) as u8) as char);
+        index /= 26;
+    }
+    label.iter().rev().collect()
+}
+
+impl FromStr for LicenseNode {
+    type Err = ParseError;
+
+    /// The whole input has to be a single root node
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut numbers = input
+            .split_whitespace()
+            .enumerate()
+            .map(|(position, token)| {
+                token.parse().map_err(|_| ParseError::InvalidNumber {
+                    position,
+                    token: token.to_string(),
+                })
+            });
+        let mut read = 0;
+        let mut next = |what| {
+            read += 1;
+            numbers.next().unwrap_or(Err(ParseError::Truncated {
+                position: read - 1,
+                what,
+            }))
+        };
+        let header = |next: &mut dyn FnMut(&'static str) -> Result<usize, ParseError>| {
+            Ok(Partial {
+                missing_children: next("child count")?,
+                metadata: next("metadata count")?,
+                children: vec![],
+            })
+        };
+
+        let mut stack = vec![header(&mut next)?];
+        loop {
+            let top = stack.last_mut().unwrap();
+            if top.missing_children > 0 {
+                top.missing_children -= 1;
+                let child = header(&mut next)?;
+                stack.push(child);
+                continue;
+            }
+
+            let partial = stack.pop().unwrap();
+            let metadata = (0..partial.metadata)
+                .map(|_| next("metadata entry"))
+                .collect::<Result<_, _>>()?;
+            let node = LicenseNode {
+                children: partial.children,
+                metadata,
+            };
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => {
+                    let position = read;
+                    let remaining = numbers.count();
+                    return if remaining > 0 {
+                        Err(ParseError::Trailing {
+                            position,
+                            remaining,
+                        })
+                    } else {
+                        Ok(node)
+                    };
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidNumber {
+        position: usize,
+        token: String,
+    },
+    /// the input ended where a number was expected
+    Truncated {
+        position: usize,
+        what: &'static str,
+    },
+    /// numbers left after the root node is complete
+    Trailing {
+        position: usize,
+        remaining: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::InvalidNumber { position, token } => {
+                write!(f, "number {}: '{}' is not a number", position + 1, token)
+            }
+            ParseError::Truncated { position, what } => {
+                write!(
+                    f,
+                    "number {}: input ended, expected a {}",
+                    position + 1,
+                    what
+                )
+            }
+            ParseError::Trailing {
+                position,
+                remaining,
+            } => write!(
+                f,
+                "number {}: {} numbers left after the root node",
+                position + 1,
+                remaining
+            ),
+        }
+    }
+}
+
+impl fmt::Display for LicenseNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let numbers: Vec<_> = self.to_numbers().iter().map(usize::to_string).collect();
+        write!(f, "{}", numbers.join(" "))
+    }
+}
+
+impl<'a> fmt::Display for Outline<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.root
+            .nodes()
+            .into_iter()
+            .zip(self.root.values())
+            .try_for_each(|((label, path, node), value)| {
+                let metadata: Vec<_> = node.metadata.iter().map(usize::to_string).collect();
+                writeln!(
+                    f,
+                    "{:indent$}{} metadata: [{}] value: {}",
+                    "",
+                    label,
+                    metadata.join(", "),
+                    value,
+                    indent = path.len() * 2
+                )
+            })
+    }
+}
+
+impl fmt::Display for DanglingReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path: Vec<_> = self.path.iter().map(usize::to_string).collect();
+        write!(
+            f,
+            "node {} at /{}: metadata entry {} but only {} children",
+            self.label,
+            path.join("/"),
+            self.entry,
+            self.children
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+
+    #[test]
+    fn sample() {
+        let tree: LicenseNode = SAMPLE.parse().unwrap();
+        assert_eq!(tree.metadata_sum(), 138);
+        assert_eq!(tree.value(), 66);
+        assert_eq!(
+            tree.stats(),
+            TreeStats {
+                nodes: 4,
+                depth: 3,
+                metadata_entries: 8
+            }
+        );
+        assert_eq!(tree.children[1].children[0], LicenseNode::leaf(vec![99]));
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(SAMPLE.parse::<LicenseNode>().unwrap().to_string(), SAMPLE);
+
+        let deep = (0..200).fold(LicenseNode::leaf(vec![1]), |child, i| LicenseNode {
+            children: vec![child, LicenseNode::leaf(vec![])],
+            metadata: vec![i % 3],
+        });
+        let parsed: LicenseNode = deep.to_string().parse().unwrap();
+        assert_eq!(parsed.stats().depth, 201);
+        assert_eq!(parsed.to_numbers(), deep.to_numbers());
+    }
+
+    #[test]
+    fn deep_chain() {
+        let deep = (0..100_000).fold(LicenseNode::leaf(vec![1]), |child, _| LicenseNode {
+            children: vec![child],
+            metadata: vec![1],
+        });
+        assert_eq!(deep.metadata_sum(), 100_001);
+        assert_eq!(deep.value(), 1);
+        assert_eq!(deep.stats().depth, 100_001);
+
+        let numbers = deep.to_numbers();
+        assert_eq!(numbers.len(), 3 * 100_001);
+        assert_eq!(numbers[..4], [1, 1, 1, 1]);
+        let parsed: LicenseNode = deep.to_string().parse().unwrap();
+        assert_eq!(parsed.to_numbers(), numbers);
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1".parse::<LicenseNode>(),
+            Err(ParseError::Truncated {
+                position: 15,
+                what: "metadata entry"
+            })
+        );
+        assert_eq!(
+            "1 1 0".parse::<LicenseNode>(),
+            Err(ParseError::Truncated {
+                position: 3,
+                what: "metadata count"
+            })
+        );
+        assert_eq!(
+            "0 1 5 7 8".parse::<LicenseNode>(),
+            Err(ParseError::Trailing {
+                position: 3,
+                remaining: 2
+            })
+        );
+        assert_eq!(
+            "0 1 x".parse::<LicenseNode>().unwrap_err().to_string(),
+            "number 3: 'x' is not a number"
+        );
+        assert!("".parse::<LicenseNode>().is_err());
+    }
+
+    #[test]
+    fn outline() {
+        let tree: LicenseNode = SAMPLE.parse().unwrap();
+        assert_eq!(
+            tree.outline().to_string(),
+            r"A metadata: [1, 1, 2] value: 66
+  B metadata: [10, 11, 12] value: 33
+  C metadata: [2] value: 0
+    D metadata: [99] value: 99
+"
+        );
+        let deep = (0..200).fold(LicenseNode::leaf(vec![4]), |child, i| LicenseNode {
+            children: vec![LicenseNode::leaf(vec![i]), child],
+            metadata: vec![i % 3, 1],
+        });
+        for tree in &[tree, deep] {
+            let values: Vec<_> = tree.nodes().iter().map(|(_, _, n)| n.value()).collect();
+            assert_eq!(tree.values(), values);
+        }
+
+        assert_eq!(label(25), "Z");
+        assert_eq!(label(26), "AA");
+        assert_eq!(label(27 * 26), "AAA");
+    }
+
+    #[test]
+    fn queries() {
+        let tree: LicenseNode = SAMPLE.parse().unwrap();
+        assert_eq!(tree.node_at(&[]).map(LicenseNode::value), Some(66));
+        assert_eq!(tree.node_at(&[2, 1]).map(LicenseNode::value), Some(99));
+        assert_eq!(tree.node_at(&[2, 2]), None);
+        assert_eq!(tree.node_at(&[0]), None);
+
+        let dangling = tree.dangling_references();
+        assert_eq!(
+            dangling,
+            vec![DanglingReference {
+                label: "C".to_string(),
+                path: vec![2],
+                entry: 2,
+                children: 1,
+            }]
+        );
+        assert_eq!(
+            dangling[0].to_string(),
+            "node C at /2: metadata entry 2 but only 1 children"
+        );
+    }
+}
@@ -22,3 +22,36 @@ macro_rules! read_main {
         }
     };
 }
+
+/// `read_main!` for simulations that can record frames: when the `AOC_FRAMES` variables ask for
+/// a recording `solve_recorded` gets the configured `Recorder` and fails if a frame can't be
+/// written, otherwise `solve` runs as usual
+#[macro_export]
+macro_rules! record_main {
+    () => {
+        fn main() {
+            use std::io::Read;
+
+            let frames = match $crate::frame_render::Recorder::from_env() {
+                Ok(frames) => frames,
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input).unwrap();
+            if frames.is_disabled() {
+                println!("{}", solve(input.as_str()));
+                return;
+            }
+            match solve_recorded(input.as_str(), frames) {
+                Ok(answer) => println!("{}", answer),
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+    };
+}
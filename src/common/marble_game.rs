@@ -0,0 +1,221 @@
+use super::circle::Circle;
+
+use std::fmt;
+
+/// How marbles get scored, the puzzle's rules are the default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// marbles numbered by a multiple of this are kept instead of placed
+    pub special: u32,
+    /// how far counter-clockwise from the current marble the marble removed with a special one is
+    pub skip: usize,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            special: 23,
+            skip: 7,
+        }
+    }
+}
+
+/// A game in progress, starting with only marble 0 in the circle
+#[derive(Debug, Clone)]
+pub struct Game {
+    rules: Rules,
+    circle: Circle<u32>,
+    scores: Vec<u64>,
+    played: u32,
+}
+
+impl Game {
+    pub fn new(players: usize, rules: Rules) -> Self {
+        Game::with_capacity(players, rules, 0)
+    }
+
+    /// A game with room for `marbles` to be played without reallocating
+    pub fn with_capacity(players: usize, rules: Rules, marbles: usize) -> Self {
+        assert!(players > 0, "the game needs at least one player");
+        // with every marble special the first one empties the circle
+        assert!(
+            rules.special > 1,
+            "the special marble divisor has to be at least 2"
+        );
+
+        let mut circle = Circle::with_capacity(marbles + 1);
+        circle.insert_after(0);
+        Game {
+            rules,
+            circle,
+            scores: vec![0; players],
+            played: 0,
+        }
+    }
+
+    /// Plays the next marble, returning what its player scored
+    pub fn play(&mut self) -> u64 {
+        self.played += 1;
+        let marble = self.played;
+        if marble % self.rules.special != 0 {
+            self.circle.clockwise(1).insert_after(marble);
+            return 0;
+        }
+
+        let removed = self
+            .circle
+            .counter_clockwise(self.rules.skip)
+            .remove()
+            .unwrap();
+        let scored = u64::from(marble) + u64::from(removed);
+        let player = (marble as usize - 1) % self.scores.len();
+        self.scores[player] += scored;
+        scored
+    }
+
+    /// Plays until `last_marble` has been placed, returning the winning score
+    pub fn play_until(&mut self, last_marble: u32) -> u64 {
+        while self.played < last_marble {
+            self.play();
+        }
+        self.high_score()
+    }
+
+    pub fn scores(&self) -> &[u64] {
+        &self.scores
+    }
+
+    pub fn high_score(&self) -> u64 {
+        self.scores.iter().cloned().max().unwrap_or(0)
+    }
+
+    /// The player that placed the last marble, 1-based
+    pub fn last_player(&self) -> Option<usize> {
+        match self.played {
+            0 => None,
+            played => Some((played as usize - 1) % self.scores.len() + 1),
+        }
+    }
+}
+
+impl fmt::Display for Game {
+    /// The circle as the puzzle shows it: the last player, then every marble clockwise from
+    /// marble 0 with the current marble in parentheses, e.g. `[4]  0 (4) 2  1  3 `
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.last_player() {
+            Some(player) => write!(f, "[{}]", player)?,
+            None => write!(f, "[-]")?,
+        }
+
+        // the circle iterates from the current marble, rotate it to start at the lowest one
+        let marbles: Vec<_> = self.circle.iter().collect();
+        let start = (0..marbles.len()).min_by_key(|&i| marbles[i]).unwrap_or(0);
+        let mut after_current = false;
+        for (i, marble) in marbles
+            .iter()
+            .cycle()
+            .skip(start)
+            .take(marbles.len())
+            .enumerate()
+        {
+            let current = (start + i) % marbles.len() == 0;
+            let marble = if current {
+                format!("({}", marble)
+            } else {
+                marble.to_string()
+            };
+            if after_current {
+                write!(f, "){:>2}", marble)?;
+            } else {
+                write!(f, "{:>3}", marble)?;
+            }
+            after_current = current;
+        }
+        write!(f, "{}", if after_current { ")" } else { " " })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        let examples = [
+            (9, 25, 32),
+            (10, 1618, 8317),
+            (13, 7999, 146_373),
+            (17, 1104, 2764),
+            (21, 6111, 54718),
+            (30, 5807, 37305),
+        ];
+        for &(players, last_marble, high_score) in examples.iter() {
+            let mut game = Game::new(players, Rules::default());
+            assert_eq!(game.play_until(last_marble), high_score);
+        }
+    }
+
+    #[test]
+    fn replay() {
+        let mut game = Game::new(9, Rules::default());
+        let mut turns = vec![game.to_string()];
+        let scored: u64 = (0..25)
+            .map(|_| {
+                let scored = game.play();
+                turns.push(game.to_string());
+                scored
+            })
+            .sum();
+        assert_eq!(scored, 32);
+        assert_eq!(game.scores()[4], 32);
+        assert_eq!(turns[0], "[-] (0)");
+        assert_eq!(turns[1], "[1]  0 (1)");
+        assert_eq!(turns[2], "[2]  0 (2) 1 ");
+        assert_eq!(turns[3], "[3]  0  2  1 (3)");
+        assert_eq!(turns[10], "[1]  0  8  4  9  2(10) 5  1  6  3  7 ");
+        assert_eq!(
+            turns[22],
+            "[4]  0 16  8 17  4 18  9 19  2 20 10 21  5(22)11  1 12  6 13  3 14  7 15 "
+        );
+        assert_eq!(
+            turns[23],
+            "[5]  0 16  8 17  4 18(19) 2 20 10 21  5 22 11  1 12  6 13  3 14  7 15 "
+        );
+        assert_eq!(
+            turns[25],
+            "[7]  0 16  8 17  4 18 19  2 24 20(25)10 21  5 22 11  1 12  6 13  3 14  7 15 "
+        );
+    }
+
+    #[test]
+    fn custom_rules() {
+        let rules = Rules {
+            special: 5,
+            skip: 2,
+        };
+        let mut game = Game::new(2, rules);
+        let turns: Vec<_> = (0..5).map(|_| game.play()).collect();
+        assert_eq!(turns, vec![0, 0, 0, 0, 5 + 3]);
+        assert_eq!(game.to_string(), "[1] (0) 4  2  1 ");
+        assert_eq!(game.scores(), &[8, 0][..]);
+
+        let rules = Rules {
+            special: 2,
+            skip: 3,
+        };
+        // only the last odd marble stays, each even marble scores with the odd one before it
+        let mut game = Game::new(3, rules);
+        game.play_until(1000);
+        assert_eq!(game.scores().iter().sum::<u64>(), 499_501);
+    }
+
+    #[test]
+    #[should_panic]
+    fn every_marble_special() {
+        let rules = Rules {
+            special: 1,
+            skip: 7,
+        };
+        Game::new(2, rules);
+    }
+}
@@ -0,0 +1,302 @@
+use std::{collections::VecDeque, fmt};
+
+/// Fully reacts a polymer: whenever two adjacent units react they are both destroyed, which may
+/// bring together two more units that react in turn. Units are kept on a stack so every unit is
+/// pushed and popped at most once.
+pub fn reduce<T: Copy>(
+    units: impl IntoIterator<Item = T>,
+    reacts: impl Fn(T, T) -> bool,
+) -> Vec<T> {
+    units.into_iter().fold(vec![], |mut reduced, unit| {
+        react(&mut reduced, unit, &reacts);
+        reduced
+    })
+}
+
+/// Adds `unit` to the reduced polymer, returns the unit it destroyed if it reacted
+fn react<T: Copy>(reduced: &mut Vec<T>, unit: T, reacts: impl Fn(T, T) -> bool) -> Option<T> {
+    match reduced.last() {
+        Some(&last) if reacts(last, unit) => reduced.pop(),
+        _ => {
+            reduced.push(unit);
+            None
+        }
+    }
+}
+
+/// A polymer reduced as it is streamed in, with checkpoints to undo pushed units. Undo history
+/// is only kept back to the oldest open checkpoint, so streaming without checkpoints takes no
+/// memory beyond the reduced polymer.
+pub struct Reactor<T, F> {
+    reduced: Vec<T>,
+    /// how to undo every unit pushed since the oldest open checkpoint, oldest first
+    undo: VecDeque<Undo<T>>,
+    pushed: usize,
+    /// checkpoints that can still be rolled back to, oldest first
+    open: Vec<Checkpoint>,
+    generation: u64,
+    reacts: F,
+}
+
+/// The state of a `Reactor` to go back to with `rollback`, open until it is released or an
+/// earlier checkpoint is rolled back to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    generation: u64,
+    pushed: usize,
+}
+
+/// The checkpoint was released or discarded by rolling back to an earlier one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleCheckpoint;
+
+enum Undo<T> {
+    Pop,
+    Restore(T),
+}
+
+impl Reactor<u8, fn(u8, u8) -> bool> {
+    /// A reactor with the puzzle's reaction rule
+    pub fn ascii() -> Self {
+        Reactor::new(ascii_polarity)
+    }
+}
+
+impl<T: Copy, F: Fn(T, T) -> bool> Reactor<T, F> {
+    pub fn new(reacts: F) -> Self {
+        Reactor {
+            reduced: vec![],
+            undo: VecDeque::new(),
+            pushed: 0,
+            open: vec![],
+            generation: 0,
+            reacts,
+        }
+    }
+
+    /// Reacts `units` with what has been pushed so far, same result as `reduce` on everything
+    pub fn push(&mut self, units: &[T]) {
+        for &unit in units {
+            let destroyed = react(&mut self.reduced, unit, &self.reacts);
+            if !self.open.is_empty() {
+                self.undo.push_back(match destroyed {
+                    Some(destroyed) => Undo::Restore(destroyed),
+                    None => Undo::Pop,
+                });
+            }
+        }
+        self.pushed += units.len();
+    }
+
+    /// Length of the polymer reduced so far
+    pub fn len(&self) -> usize {
+        self.reduced.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reduced.is_empty()
+    }
+
+    pub fn reduced(&self) -> &[T] {
+        &self.reduced
+    }
+
+    /// Starts keeping undo history until the checkpoint is released
+    pub fn checkpoint(&mut self) -> Checkpoint {
+        self.generation += 1;
+        let checkpoint = Checkpoint {
+            generation: self.generation,
+            pushed: self.pushed,
+        };
+        self.open.push(checkpoint);
+        checkpoint
+    }
+
+    /// Undoes every unit pushed since `checkpoint`, which stays open. Checkpoints taken after
+    /// it are discarded.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) -> Result<(), StaleCheckpoint> {
+        if !self.open.contains(&checkpoint) {
+            return Err(StaleCheckpoint);
+        }
+
+        let reduced = &mut self.reduced;
+        let undone = self.pushed - checkpoint.pushed;
+        let kept = self.undo.len() - undone;
+        self.undo.drain(kept..).rev().for_each(|undo| match undo {
+            Undo::Pop => {
+                reduced.pop();
+            }
+            Undo::Restore(unit) => reduced.push(unit),
+        });
+        self.pushed = checkpoint.pushed;
+        self.open.retain(|c| c.generation <= checkpoint.generation);
+        Ok(())
+    }
+
+    /// Closes `checkpoint`, dropping the undo history no open checkpoint needs anymore
+    pub fn release(&mut self, checkpoint: Checkpoint) -> Result<(), StaleCheckpoint> {
+        let position = self.open.iter().position(|&c| c == checkpoint);
+        self.open.remove(position.ok_or(StaleCheckpoint)?);
+
+        let oldest = self.open.first().map_or(self.pushed, |c| c.pushed);
+        let needed = self.pushed - oldest;
+        let unneeded = self.undo.len() - needed;
+        self.undo.drain(..unneeded);
+        Ok(())
+    }
+}
+
+impl fmt::Display for StaleCheckpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "checkpoint was released or rolled back past")
+    }
+}
+
+/// `reduce` over the characters of a UTF-8 polymer
+pub fn reduce_str(polymer: &str, reacts: impl Fn(char, char) -> bool) -> String {
+    reduce(polymer.chars(), reacts).into_iter().collect()
+}
+
+/// Length of the fully reacted polymer with the puzzle's reaction rule
+pub fn polymer_len(input: &[u8]) -> usize {
+    reduce(input.iter().cloned(), ascii_polarity).len()
+}
+
+/// The puzzle's rule: the same letter in opposite case reacts
+pub fn ascii_polarity(a: u8, b: u8) -> bool {
+    a != b && a.eq_ignore_ascii_case(&b)
+}
+
+/// The puzzle's rule for any alphabet with case, `é` reacts with `É`
+pub fn unicode_polarity(a: char, b: char) -> bool {
+    a != b && a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// Units react only with their partner in one of `pairs`, in either order
+pub fn pairs<T: Copy + PartialEq>(pairs: &[(T, T)]) -> impl Fn(T, T) -> bool + '_ {
+    move |a, b| {
+        pairs
+            .iter()
+            .any(|&(x, y)| (a == x && b == y) || (a == y && b == x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic polymer of the letters a to d, mostly reacting
+    fn polymer(len: usize) -> Vec<u8> {
+        let mut seed = 0x2545_f491_u32;
+        (0..len)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                let unit = b'a' + (seed >> 16) as u8 % 4;
+                if seed >> 30 & 1 == 0 {
+                    unit.to_ascii_uppercase()
+                } else {
+                    unit
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reduced_polymer() {
+        let reduced = reduce("dabAcCaCBAcCcaDA".bytes(), ascii_polarity);
+        assert_eq!(reduced, b"dabCBAcaDA");
+        assert_eq!(polymer_len(b"aBbA"), 0);
+        assert_eq!(polymer_len(b"abAB"), 4);
+    }
+
+    #[test]
+    fn custom_rules() {
+        assert_eq!(reduce_str("éaAÉxÉé", unicode_polarity), "x");
+        assert_eq!(
+            reduce_str("ᚠᚢᚦᚢᚠ", pairs(&[('ᚠ', 'ᚦ'), ('ᚢ', 'ᚢ')])),
+            "ᚠᚢᚦᚢᚠ"
+        );
+        assert_eq!(reduce_str("ᚠᚢᚢᚦ", pairs(&[('ᚠ', 'ᚦ'), ('ᚢ', 'ᚢ')])), "");
+
+        let codons = ["AUG", "UAC", "GGC", "CCG", "UAC", "AUG"];
+        let reduced = reduce(
+            codons.iter().cloned(),
+            pairs(&[("AUG", "UAC"), ("GGC", "CCG")]),
+        );
+        assert!(reduced.is_empty());
+    }
+
+    #[test]
+    fn incremental() {
+        let input = polymer(2000);
+        let mut reactor = Reactor::ascii();
+        let mut pushed = 0;
+        let mut chunk = 0;
+        while pushed < input.len() {
+            chunk += 1;
+            let end = (pushed + chunk % 37).min(input.len());
+            reactor.push(&input[pushed..end]);
+            pushed = end;
+            assert_eq!(reactor.len(), polymer_len(&input[..pushed]));
+        }
+        assert_eq!(reactor.reduced(), &reduce(input, ascii_polarity)[..]);
+    }
+
+    #[test]
+    fn rollback() {
+        let input = polymer(600);
+        let mut reactor = Reactor::ascii();
+        let empty = reactor.checkpoint();
+        reactor.push(&input[..200]);
+        let first = reactor.checkpoint();
+        reactor.push(&input[200..400]);
+        let second = reactor.checkpoint();
+        reactor.push(&input[400..]);
+
+        reactor.rollback(second).unwrap();
+        assert_eq!(
+            reactor.reduced(),
+            &reduce(input[..400].iter().cloned(), ascii_polarity)[..]
+        );
+        reactor.rollback(first).unwrap();
+        assert_eq!(
+            reactor.reduced(),
+            &reduce(input[..200].iter().cloned(), ascii_polarity)[..]
+        );
+
+        reactor.push(b"xX");
+        assert_eq!(reactor.len(), polymer_len(&input[..200]));
+        assert_eq!(reactor.rollback(second), Err(StaleCheckpoint));
+        reactor.rollback(empty).unwrap();
+        assert!(reactor.is_empty());
+    }
+
+    #[test]
+    fn released_history() {
+        let input = polymer(600);
+        let mut reactor = Reactor::ascii();
+        reactor.push(&input[..200]);
+        assert!(reactor.undo.is_empty());
+
+        let first = reactor.checkpoint();
+        reactor.push(&input[200..300]);
+        let second = reactor.checkpoint();
+        reactor.push(&input[300..400]);
+        assert_eq!(reactor.undo.len(), 200);
+
+        reactor.release(first).unwrap();
+        assert_eq!(reactor.undo.len(), 100);
+        assert_eq!(reactor.rollback(first), Err(StaleCheckpoint));
+        assert_eq!(reactor.release(first), Err(StaleCheckpoint));
+
+        reactor.rollback(second).unwrap();
+        assert_eq!(
+            reactor.reduced(),
+            &reduce(input[..300].iter().cloned(), ascii_polarity)[..]
+        );
+        reactor.release(second).unwrap();
+        reactor.push(&input[300..]);
+        assert!(reactor.undo.is_empty());
+        assert_eq!(reactor.reduced(), &reduce(input, ascii_polarity)[..]);
+    }
+}
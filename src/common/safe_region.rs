@@ -0,0 +1,160 @@
+/// Number of grid points whose total Manhattan distance to all of `points` is below `threshold`
+pub fn region_size(points: &[(i64, i64)], threshold: u64) -> u64 {
+    let xs = axis_sums(points.iter().map(|&(x, _)| x), threshold);
+    let ys = axis_sums(points.iter().map(|&(_, y)| y), threshold);
+    count_pairs(&xs, &ys, u128::from(threshold))
+}
+
+/// `region_size` for points in space, one pass over the x and y sums per z plane
+pub fn region_size_3d(points: &[(i64, i64, i64)], threshold: u64) -> u64 {
+    let xs = axis_sums(points.iter().map(|&(x, _, _)| x), threshold);
+    let ys = axis_sums(points.iter().map(|&(_, y, _)| y), threshold);
+    let zs = axis_sums(points.iter().map(|&(_, _, z)| z), threshold);
+    zs.iter()
+        .map(|&z| count_pairs(&xs, &ys, u128::from(threshold) - z))
+        .sum()
+}
+
+/// Distances along a single axis add up independently of the other axes, so the total distance
+/// of a point is the sum of one value per axis. Returns, in ascending order, the sum of distances
+/// to `values` from every position on the axis where that sum alone is below `threshold`.
+fn axis_sums(values: impl Iterator<Item = i64>, threshold: u64) -> Vec<u128> {
+    let mut values: Vec<_> = values.collect();
+    values.sort_unstable();
+    let threshold = u128::from(threshold);
+    let median = match values.get(values.len() / 2) {
+        Some(&m) => m,
+        None => return vec![],
+    };
+    let start: u128 = values
+        .iter()
+        .map(|&v| (i128::from(v.max(median)) - i128::from(v.min(median))) as u128)
+        .sum();
+    if start >= threshold {
+        return vec![];
+    }
+
+    // moving one step changes the sum by the number of values behind minus the ones ahead
+    let n = values.len() as u128;
+    let mut right = vec![];
+    let (mut position, mut sum, mut behind) = (median, start, 0);
+    loop {
+        while behind < values.len() && values[behind] <= position {
+            behind += 1;
+        }
+        sum = sum + 2 * behind as u128 - n;
+        position += 1;
+        if sum >= threshold {
+            break;
+        }
+        right.push(sum);
+    }
+
+    // walking left, the values before `trailing` are the ones still below the position
+    let mut left = vec![];
+    let (mut position, mut sum, mut trailing) = (median, start, values.len());
+    loop {
+        while trailing > 0 && values[trailing - 1] >= position {
+            trailing -= 1;
+        }
+        sum = sum + n - 2 * trailing as u128;
+        position -= 1;
+        if sum >= threshold {
+            break;
+        }
+        left.push(sum);
+    }
+
+    let mut sums = Vec::with_capacity(left.len() + right.len() + 1);
+    sums.push(start);
+    let (mut l, mut r) = (0, 0);
+    while l < left.len() || r < right.len() {
+        if r == right.len() || (l < left.len() && left[l] <= right[r]) {
+            sums.push(left[l]);
+            l += 1;
+        } else {
+            sums.push(right[r]);
+            r += 1;
+        }
+    }
+    sums
+}
+
+/// Pairs of one sum from each sorted slice adding up to less than `threshold`
+fn count_pairs(a: &[u128], b: &[u128], threshold: u128) -> u64 {
+    let mut below = b.len();
+    a.iter()
+        .map(|&x| {
+            while below > 0 && x + b[below - 1] >= threshold {
+                below -= 1;
+            }
+            below as u64
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::Lcg;
+
+    fn distance(a: &[i64], b: &[i64]) -> u64 {
+        a.iter().zip(b).map(|(x, y)| (x - y).abs() as u64).sum()
+    }
+
+    #[test]
+    fn sample() {
+        let points = [(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)];
+        assert_eq!(region_size(&points, 32), 16);
+        assert_eq!(region_size(&points, 0), 0);
+        assert_eq!(region_size(&[], 10), 0);
+    }
+
+    #[test]
+    fn matches_grid_scan() {
+        let mut rng = Lcg::new(11);
+        let mut next = |bound| rng.below(bound);
+        for _ in 0..20 {
+            let n = 1 + next(6) as usize;
+            let threshold = next(25) as u64;
+            let points: Vec<_> = (0..n)
+                .map(|_| (next(11) - 5, next(11) - 5, next(11) - 5))
+                .collect();
+            let planar: Vec<_> = points.iter().map(|&(x, y, _)| (x, y)).collect();
+
+            let range = || -30..30;
+            let flat = range()
+                .flat_map(|x| range().map(move |y| [x, y]))
+                .filter(|p| {
+                    planar
+                        .iter()
+                        .map(|&(x, y)| distance(p, &[x, y]))
+                        .sum::<u64>()
+                        < threshold
+                })
+                .count() as u64;
+            assert_eq!(region_size(&planar, threshold), flat);
+
+            let solid = range()
+                .flat_map(|x| range().flat_map(move |y| range().map(move |z| [x, y, z])))
+                .filter(|p| {
+                    points
+                        .iter()
+                        .map(|&(x, y, z)| distance(p, &[x, y, z]))
+                        .sum::<u64>()
+                        < threshold
+                })
+                .count() as u64;
+            assert_eq!(region_size_3d(&points, threshold), solid);
+        }
+    }
+
+    #[test]
+    fn large_threshold() {
+        let t = 1_000_000;
+        assert_eq!(
+            region_size(&[(-5, i64::from(i32::max_value()))], t),
+            2 * t * t - 2 * t + 1
+        );
+    }
+}
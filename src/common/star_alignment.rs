@@ -0,0 +1,222 @@
+use std::ops::Range;
+
+/// A point of light moving at a constant velocity every second
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Star {
+    pub position: (i64, i64),
+    pub velocity: (i64, i64),
+}
+
+impl Star {
+    pub fn at(&self, second: i64) -> (i64, i64) {
+        (
+            self.position.0 + self.velocity.0 * second,
+            self.position.1 + self.velocity.1 * second,
+        )
+    }
+}
+
+/// Top left and bottom right corners of the box around every star at `second`
+pub fn bounds(stars: &[Star], second: i64) -> Option<((i64, i64), (i64, i64))> {
+    let mut positions = stars.iter().map(|s| s.at(second));
+    let first = positions.next()?;
+    Some(positions.fold((first, first), |(min, max), (x, y)| {
+        ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+    }))
+}
+
+/// Width and height of the box around every star at `second`
+fn extent(stars: &[Star], second: i64) -> (i128, i128) {
+    bounds(stars, second).map_or((0, 0), |(min, max)| {
+        (
+            i128::from(max.0) - i128::from(min.0) + 1,
+            i128::from(max.1) - i128::from(min.1) + 1,
+        )
+    })
+}
+
+/// Number of cells in the box around every star at `second`
+pub fn area(stars: &[Star], second: i64) -> u128 {
+    let (width, height) = extent(stars, second);
+    (width * height) as u128
+}
+
+/// The area around the stars at every second of `seconds`
+pub fn area_curve(stars: &[Star], seconds: Range<i64>) -> impl Iterator<Item = (i64, u128)> + '_ {
+    seconds.map(move |second| (second, area(stars, second)))
+}
+
+/// When the stars are closest together on average: the spread of the stars around their center is
+/// a parabola over time, this is its lowest point. `None` if they all move the same way.
+pub fn estimate(stars: &[Star]) -> Option<f64> {
+    let n = stars.len() as f64;
+    let mean =
+        |value: &dyn Fn(&Star) -> i64| stars.iter().map(|s| value(s) as f64).sum::<f64>() / n;
+    let (px, py) = (mean(&|s| s.position.0), mean(&|s| s.position.1));
+    let (vx, vy) = (mean(&|s| s.velocity.0), mean(&|s| s.velocity.1));
+
+    let (towards, speed) = stars.iter().fold((0.0, 0.0), |(towards, speed), s| {
+        let (dpx, dpy) = (s.position.0 as f64 - px, s.position.1 as f64 - py);
+        let (dvx, dvy) = (s.velocity.0 as f64 - vx, s.velocity.1 as f64 - vy);
+        (
+            towards + dpx * dvx + dpy * dvy,
+            speed + dvx * dvx + dvy * dvy,
+        )
+    });
+    if speed > 0.0 {
+        Some(-towards / speed)
+    } else {
+        None
+    }
+}
+
+/// The second, not before 0, at which the box around the stars is smallest, the earliest on ties.
+///
+/// Width and height are each convex over time, so the box shrinks until the earlier of their
+/// lowest points and grows after the later one. In between one shrinks while the other grows,
+/// which leaves the area concave wherever neither changes pace.
+pub fn convergence_time(stars: &[Star]) -> i64 {
+    let narrowest = lowest(|second| extent(stars, second).0);
+    let flattest = lowest(|second| extent(stars, second).1);
+    smallest_between(stars, narrowest.min(flattest), narrowest.max(flattest)).1
+}
+
+/// The earliest second, not before 0, at which the convex `f` is lowest
+fn lowest(f: impl Fn(i64) -> i128) -> i64 {
+    let rising = |second| f(second + 1) >= f(second);
+    let mut last = 1;
+    while !rising(last) {
+        last *= 2;
+    }
+
+    let mut first = 0;
+    while first < last {
+        let mid = first + (last - first) / 2;
+        if rising(mid) {
+            last = mid;
+        } else {
+            first = mid + 1;
+        }
+    }
+    first
+}
+
+/// `(area, second)` of the earliest smallest box within `from..=to`, which must lie between the
+/// lowest width and the lowest height. Halves the range until width and height both change at a
+/// steady pace throughout, then the smaller end is the answer.
+fn smallest_between(stars: &[Star], from: i64, to: i64) -> (u128, i64) {
+    let ends = (area(stars, from), from).min((area(stars, to), to));
+    if to - from <= 1 {
+        return ends;
+    }
+
+    let mid = from + (to - from) / 2;
+    let (a, m, b) = (extent(stars, from), extent(stars, mid), extent(stars, to));
+    // a convex function meeting the line between its ends anywhere inside follows it all along
+    let steady = |a: i128, m: i128, b: i128| {
+        (m - a) * i128::from(to - from) == (b - a) * i128::from(mid - from)
+    };
+    if steady(a.0, m.0, b.0) && steady(a.1, m.1, b.1) {
+        ends
+    } else {
+        smallest_between(stars, from, mid).min(smallest_between(stars, mid, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::Lcg;
+
+    fn converging(seed: u64, count: usize, at: i64) -> Vec<Star> {
+        let mut rng = Lcg::new(seed);
+        let mut next = |bound| rng.below(bound);
+        (0..count)
+            .map(|_| {
+                let target = (next(60), next(10));
+                let velocity = (next(11) - 5, next(11) - 5);
+                Star {
+                    position: (target.0 - velocity.0 * at, target.1 - velocity.1 * at),
+                    velocity,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn small() {
+        let stars = [
+            Star {
+                position: (0, 0),
+                velocity: (1, 1),
+            },
+            Star {
+                position: (10, 10),
+                velocity: (-1, -1),
+            },
+        ];
+        assert_eq!(bounds(&stars, 2), Some(((2, 2), (8, 8))));
+        assert_eq!(area(&stars, 5), 1);
+        assert_eq!(estimate(&stars), Some(5.0));
+        assert_eq!(convergence_time(&stars), 5);
+        assert_eq!(
+            area_curve(&stars, 4..7).collect::<Vec<_>>(),
+            vec![(4, 9), (5, 1), (6, 9)]
+        );
+        assert_eq!(convergence_time(&stars[..1]), 0);
+        assert_eq!(bounds(&[], 0), None);
+    }
+
+    #[test]
+    fn matches_stepping() {
+        for seed in 0..20 {
+            let stars = converging(seed, 40, 10 + seed as i64 * 7);
+            let (_, stepped) = area_curve(&stars, 0..400)
+                .map(|(second, area)| (area, second))
+                .min()
+                .unwrap();
+            assert_eq!(convergence_time(&stars), stepped);
+        }
+    }
+
+    #[test]
+    fn separate_minimums() {
+        // the columns line up at 7 while the rows drift apart from the start
+        let stars = [
+            Star {
+                position: (-10, 5),
+                velocity: (1, 0),
+            },
+            Star {
+                position: (-17, -7),
+                velocity: (2, -2),
+            },
+        ];
+        assert_eq!((area(&stars, 0), area(&stars, 7)), (104, 27));
+        assert_eq!(convergence_time(&stars), 7);
+
+        let mut rng = Lcg::new(9);
+        let mut next = |bound| rng.below(bound);
+        for _ in 0..500 {
+            let stars: Vec<_> = (0..2 + next(3))
+                .map(|_| Star {
+                    position: (next(41) - 20, next(41) - 20),
+                    velocity: (next(5) - 2, next(5) - 2),
+                })
+                .collect();
+            let (_, stepped) = area_curve(&stars, 0..200)
+                .map(|(second, area)| (area, second))
+                .min()
+                .unwrap();
+            assert_eq!(convergence_time(&stars), stepped, "{:?}", stars);
+        }
+    }
+
+    #[test]
+    fn far_apart() {
+        let at = 1_000_000_007;
+        let stars = converging(42, 300, at);
+        assert!((estimate(&stars).unwrap() - at as f64).abs() < 100.0);
+        assert_eq!(convergence_time(&stars), at);
+    }
+}
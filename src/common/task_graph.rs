@@ -0,0 +1,674 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap, HashMap},
+    fmt,
+    str::FromStr,
+};
+
+/// `blocker` must be finished before `dependent` can begin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub blocker: String,
+    pub dependent: String,
+}
+
+/// Steps and the steps each of them waits for, in the order they were first mentioned
+#[derive(Debug, Default)]
+pub struct TaskGraph {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    blockers: Vec<BTreeSet<usize>>,
+    dependents: Vec<BTreeSet<usize>>,
+}
+
+/// Which ready step goes first when several could start at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    Alphabetical,
+    ReverseAlphabetical,
+    FirstMentioned,
+}
+
+/// The steps that kept the graph from being ordered, each one blocking the next and the last one
+/// blocking the first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    pub cycle: Vec<String>,
+}
+
+/// A step being worked on from `start` until right before `end`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slot {
+    pub task: String,
+    pub worker: usize,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Who worked on which step and when, slots are sorted by start time and then by worker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timeline {
+    pub workers: usize,
+    pub slots: Vec<Slot>,
+}
+
+/// When a step can start if there are always enough workers, and how late it can start without
+/// delaying the last step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepTiming {
+    pub task: String,
+    pub duration: u64,
+    pub earliest_start: u64,
+    pub latest_start: u64,
+}
+
+/// Timings of every step, blockers first, with no limit on the number of workers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CriticalPath {
+    pub timings: Vec<StepTiming>,
+    /// steps that cannot be delayed at all, each one blocking the next
+    pub path: Vec<String>,
+}
+
+/// Graphviz DOT of a graph, steps can be labelled with their position in an order and the time
+/// they were worked on
+pub struct Dot<'a> {
+    graph: &'a TaskGraph,
+    order: Vec<&'a str>,
+    timeline: Option<&'a Timeline>,
+}
+
+impl TaskGraph {
+    pub fn new() -> Self {
+        TaskGraph::default()
+    }
+
+    pub fn from_dependencies(dependencies: impl IntoIterator<Item = Dependency>) -> Self {
+        dependencies
+            .into_iter()
+            .fold(TaskGraph::new(), |mut graph, dep| {
+                graph.add_dependency(&dep.blocker, &dep.dependent);
+                graph
+            })
+    }
+
+    /// Adds a step with no dependencies if it is not in the graph yet
+    pub fn add_task(&mut self, name: &str) -> usize {
+        if let Some(&i) = self.index.get(name) {
+            return i;
+        }
+        let i = self.names.len();
+        self.names.push(name.to_string());
+        self.index.insert(name.to_string(), i);
+        self.blockers.push(BTreeSet::new());
+        self.dependents.push(BTreeSet::new());
+        i
+    }
+
+    pub fn add_dependency(&mut self, blocker: &str, dependent: &str) {
+        let blocker = self.add_task(blocker);
+        let dependent = self.add_task(dependent);
+        self.blockers[dependent].insert(blocker);
+        self.dependents[blocker].insert(dependent);
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Every step, blockers first, picking among ready steps with `tie_break`
+    pub fn order(&self, tie_break: TieBreak) -> Result<Vec<&str>, CycleError> {
+        let timeline = self.schedule(1, |_| 0, tie_break)?;
+        Ok(timeline.slots.iter().map(|s| self.name(&s.task)).collect())
+    }
+
+    /// Simulates `workers` working on the steps as soon as they are ready. Steps finishing at the
+    /// same time all unblock their dependents before anyone picks up new work.
+    pub fn schedule(
+        &self,
+        workers: usize,
+        duration: impl Fn(&str) -> u64,
+        tie_break: TieBreak,
+    ) -> Result<Timeline, CycleError> {
+        assert!(workers > 0, "no one to work on the steps");
+
+        let rank = self.ranks(tie_break);
+        let mut waiting: Vec<_> = self.blockers.iter().map(BTreeSet::len).collect();
+        let mut ready: BTreeSet<_> = (0..self.len())
+            .filter(|&i| waiting[i] == 0)
+            .map(|i| (rank[i], i))
+            .collect();
+        let mut idle: BTreeSet<_> = (0..workers).collect();
+        let mut running = BinaryHeap::new();
+        let mut slots = vec![];
+        let mut time = 0;
+
+        loop {
+            while !idle.is_empty() && !ready.is_empty() {
+                let worker = *idle.iter().next().unwrap();
+                let step = *ready.iter().next().unwrap();
+                idle.remove(&worker);
+                ready.remove(&step);
+                let (_, task) = step;
+                let end = time + duration(&self.names[task]);
+                slots.push(Slot {
+                    task: self.names[task].clone(),
+                    worker,
+                    start: time,
+                    end,
+                });
+                running.push(Reverse((end, worker, task)));
+            }
+
+            time = match running.peek() {
+                Some(&Reverse((end, _, _))) => end,
+                None => break,
+            };
+            while running
+                .peek()
+                .map_or(false, |&Reverse((end, _, _))| end == time)
+            {
+                let Reverse((_, worker, task)) = running.pop().unwrap();
+                idle.insert(worker);
+                for &dependent in &self.dependents[task] {
+                    waiting[dependent] -= 1;
+                    if waiting[dependent] == 0 {
+                        ready.insert((rank[dependent], dependent));
+                    }
+                }
+            }
+        }
+
+        if slots.len() < self.len() {
+            return Err(self.cycle(&waiting));
+        }
+        slots.sort_by_key(|s| (s.start, s.worker));
+        Ok(Timeline { workers, slots })
+    }
+
+    /// Longest chain of steps, which no number of workers can finish any faster
+    pub fn critical_path(
+        &self,
+        duration: impl Fn(&str) -> u64,
+    ) -> Result<CriticalPath, CycleError> {
+        let order: Vec<_> = self
+            .order(TieBreak::FirstMentioned)?
+            .into_iter()
+            .map(|name| self.index[name])
+            .collect();
+        let durations: Vec<_> = self.names.iter().map(|n| duration(n)).collect();
+
+        let mut earliest = vec![0; self.len()];
+        for &i in &order {
+            earliest[i] = self.blockers[i]
+                .iter()
+                .map(|&b| earliest[b] + durations[b])
+                .max()
+                .unwrap_or(0);
+        }
+        let length = order
+            .iter()
+            .map(|&i| earliest[i] + durations[i])
+            .max()
+            .unwrap_or(0);
+
+        let mut latest = vec![0; self.len()];
+        for &i in order.iter().rev() {
+            let finish = self.dependents[i].iter().map(|&d| latest[d]).min();
+            latest[i] = finish.unwrap_or(length) - durations[i];
+        }
+
+        let critical = |i: &usize| earliest[*i] == latest[*i];
+        let mut path = vec![];
+        let mut next = order
+            .iter()
+            .cloned()
+            .find(|i| critical(i) && earliest[*i] == 0);
+        while let Some(i) = next {
+            path.push(self.names[i].clone());
+            let finish = earliest[i] + durations[i];
+            next = order
+                .iter()
+                .cloned()
+                .filter(|d| self.dependents[i].contains(d))
+                .find(|d| critical(d) && earliest[*d] == finish);
+        }
+
+        let timings = order
+            .into_iter()
+            .map(|i| StepTiming {
+                task: self.names[i].clone(),
+                duration: durations[i],
+                earliest_start: earliest[i],
+                latest_start: latest[i],
+            })
+            .collect();
+        Ok(CriticalPath { timings, path })
+    }
+
+    /// Edges go from blocker to dependent
+    pub fn dot(&self) -> Dot<'_> {
+        Dot {
+            graph: self,
+            order: vec![],
+            timeline: None,
+        }
+    }
+
+    fn name(&self, name: &str) -> &str {
+        &self.names[self.index[name]]
+    }
+
+    /// Position of every step in the order ready steps are picked
+    fn ranks(&self, tie_break: TieBreak) -> Vec<usize> {
+        let mut by_rank: Vec<_> = (0..self.len()).collect();
+        match tie_break {
+            TieBreak::Alphabetical => by_rank.sort_by_key(|&i| &self.names[i]),
+            TieBreak::ReverseAlphabetical => by_rank.sort_by_key(|&i| Reverse(&self.names[i])),
+            TieBreak::FirstMentioned => {}
+        }
+        let mut rank = vec![0; self.len()];
+        by_rank.iter().enumerate().for_each(|(r, &i)| rank[i] = r);
+        rank
+    }
+
+    /// Every step still waiting waits on another step still waiting, so walking blockers from
+    /// any of them has to come back around
+    fn cycle(&self, waiting: &[usize]) -> CycleError {
+        let stuck = |i: &usize| waiting[*i] > 0;
+        let mut path = vec![(0..self.len()).find(stuck).unwrap()];
+        loop {
+            let last = *path.last().unwrap();
+            let next = *self.blockers[last].iter().find(|i| stuck(i)).unwrap();
+            if let Some(start) = path.iter().position(|&i| i == next) {
+                let cycle = path[start..].iter().rev();
+                return CycleError {
+                    cycle: cycle.map(|&i| self.names[i].clone()).collect(),
+                };
+            }
+            path.push(next);
+        }
+    }
+}
+
+impl Timeline {
+    /// When the last step is done
+    pub fn duration(&self) -> u64 {
+        self.slots.iter().map(|s| s.end).max().unwrap_or(0)
+    }
+}
+
+impl StepTiming {
+    /// How long the step can be delayed without delaying the last step
+    pub fn slack(&self) -> u64 {
+        self.latest_start - self.earliest_start
+    }
+}
+
+impl CriticalPath {
+    /// Duration with unlimited workers, a lower bound for any schedule
+    pub fn length(&self) -> u64 {
+        self.timings
+            .iter()
+            .map(|t| t.earliest_start + t.duration)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl<'a> Dot<'a> {
+    pub fn with_order(self, order: &[&'a str]) -> Self {
+        Dot {
+            order: order.to_vec(),
+            ..self
+        }
+    }
+
+    pub fn with_timeline(self, timeline: &'a Timeline) -> Self {
+        Dot {
+            timeline: Some(timeline),
+            ..self
+        }
+    }
+}
+
+impl FromStr for Dependency {
+    type Err = DependencyParseError;
+
+    /// Parses `Step <blocker> must be finished before step <dependent> can begin.`
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let words: Vec<_> = input
+            .trim()
+            .trim_end_matches('.')
+            .split_whitespace()
+            .collect();
+        match words[..] {
+            ["Step", blocker, "must", "be", "finished", "before", "step", dependent, "can", "begin"] => {
+                Ok(Dependency {
+                    blocker: blocker.to_string(),
+                    dependent: dependent.to_string(),
+                })
+            }
+            _ => Err(DependencyParseError(input.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyParseError(pub String);
+
+impl fmt::Display for DependencyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "not a step dependency: '{}'", self.0)
+    }
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "steps depend on each other: ")?;
+        self.cycle
+            .iter()
+            .chain(self.cycle.first())
+            .enumerate()
+            .try_for_each(|(i, step)| {
+                if i > 0 {
+                    write!(f, " -> ")?;
+                }
+                write!(f, "{}", step)
+            })
+    }
+}
+
+impl fmt::Display for Timeline {
+    /// The table from the puzzle, with a row every time a step starts or finishes instead of
+    /// every second
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let headers: Vec<_> = (1..=self.workers)
+            .map(|w| format!("Worker {}", w))
+            .collect();
+        let task_width = self.slots.iter().map(|s| s.task.len()).max().unwrap_or(1);
+        let width = |header: &String| header.len().max(task_width);
+        let second_width = self.duration().to_string().len().max("Second".len());
+
+        write!(f, "{:>w$}", "Second", w = second_width)?;
+        headers
+            .iter()
+            .try_for_each(|h| write!(f, "   {:w$}", h, w = width(h)))?;
+        writeln!(f, "   Done")?;
+
+        let mut finished: Vec<_> = self.slots.iter().collect();
+        finished.sort_by_key(|s| (s.end, s.worker));
+        let times: BTreeSet<_> = self
+            .slots
+            .iter()
+            .flat_map(|s| vec![s.start, s.end])
+            .collect();
+        times.into_iter().try_for_each(|time| {
+            let mut row = format!("{:>w$}", time, w = second_width);
+            headers.iter().enumerate().for_each(|(worker, h)| {
+                let task = self
+                    .slots
+                    .iter()
+                    .find(|s| s.worker == worker && s.start <= time && time < s.end)
+                    .map_or(".", |s| s.task.as_str());
+                row += &format!("   {:w$}", task, w = width(h));
+            });
+            row += "   ";
+            finished
+                .iter()
+                .take_while(|s| s.end <= time)
+                .for_each(|s| row += &s.task);
+            writeln!(f, "{}", row.trim_end())
+        })
+    }
+}
+
+impl fmt::Display for CriticalPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .timings
+            .iter()
+            .map(|t| t.task.len())
+            .max()
+            .unwrap_or(0)
+            .max("Step".len());
+        writeln!(
+            f,
+            "{:w$}   Duration   Earliest   Latest   Slack",
+            "Step",
+            w = width
+        )?;
+        self.timings.iter().try_for_each(|t| {
+            writeln!(
+                f,
+                "{:w$}   {:>8}   {:>8}   {:>6}   {:>5}",
+                t.task,
+                t.duration,
+                t.earliest_start,
+                t.latest_start,
+                t.slack(),
+                w = width
+            )
+        })?;
+        writeln!(
+            f,
+            "critical path: {} ({})",
+            self.path.join(" -> "),
+            self.length()
+        )
+    }
+}
+
+impl<'a> fmt::Display for Dot<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let escape = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+        let quote = |name: &str| format!("\"{}\"", escape(name));
+
+        writeln!(f, "digraph steps {{")?;
+        self.graph.names.iter().try_for_each(|name| {
+            let mut label = vec![escape(name)];
+            if let Some(position) = self.order.iter().position(|&n| n == name) {
+                label.push(format!("#{}", position + 1));
+            }
+            let slot = self
+                .timeline
+                .and_then(|t| t.slots.iter().find(|s| &s.task == name));
+            if let Some(slot) = slot {
+                label.push(format!(
+                    "{}-{} (worker {})",
+                    slot.start,
+                    slot.end,
+                    slot.worker + 1
+                ));
+            }
+            // lines are joined after escaping so that `\n` stays a line break for Graphviz
+            writeln!(f, "    {} [label=\"{}\"];", quote(name), label.join("\\n"))
+        })?;
+        self.graph
+            .dependents
+            .iter()
+            .enumerate()
+            .flat_map(|(blocker, dependents)| dependents.iter().map(move |&d| (blocker, d)))
+            .try_for_each(|(blocker, dependent)| {
+                let names = &self.graph.names;
+                writeln!(
+                    f,
+                    "    {} -> {};",
+                    quote(&names[blocker]),
+                    quote(&names[dependent])
+                )
+            })?;
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r"Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.";
+
+    fn graph(input: &str) -> TaskGraph {
+        TaskGraph::from_dependencies(input.lines().map(|l| l.parse().unwrap()))
+    }
+
+    fn letter(name: &str) -> u64 {
+        u64::from(name.as_bytes()[0] - b'A' + 1)
+    }
+
+    #[test]
+    fn sample() {
+        let graph = graph(SAMPLE);
+        assert_eq!(
+            graph.order(TieBreak::Alphabetical).unwrap().concat(),
+            "CABDFE"
+        );
+        assert_eq!(
+            graph.order(TieBreak::ReverseAlphabetical).unwrap().concat(),
+            "CFADBE"
+        );
+        assert_eq!(
+            graph.order(TieBreak::FirstMentioned).unwrap().concat(),
+            "CAFBDE"
+        );
+
+        let timeline = graph.schedule(2, letter, TieBreak::Alphabetical).unwrap();
+        assert_eq!(timeline.duration(), 15);
+        assert_eq!(
+            timeline.to_string(),
+            r"Second   Worker 1   Worker 2   Done
+     0   C          .
+     3   A          F          C
+     4   B          F          CA
+     6   D          F          CAB
+     9   D          .          CABF
+    10   E          .          CABFD
+    15   .          .          CABFDE
+"
+        );
+    }
+
+    #[test]
+    fn named_steps() {
+        let graph = graph(
+            r"Step dig must be finished before step pour can begin.
+Step pour must be finished before step frame can begin.
+Step order must be finished before step frame can begin.",
+        );
+        let timeline = graph
+            .schedule(3, |name| name.len() as u64, TieBreak::Alphabetical)
+            .unwrap();
+        assert_eq!(timeline.duration(), 12);
+        assert_eq!(
+            timeline.slots[..2],
+            [
+                Slot {
+                    task: "dig".to_string(),
+                    worker: 0,
+                    start: 0,
+                    end: 3
+                },
+                Slot {
+                    task: "order".to_string(),
+                    worker: 1,
+                    start: 0,
+                    end: 5
+                },
+            ]
+        );
+        assert!("Step x must finish".parse::<Dependency>().is_err());
+    }
+
+    #[test]
+    fn cycles() {
+        let mut graph = graph(SAMPLE);
+        graph.add_dependency("E", "A");
+        let error = graph.order(TieBreak::Alphabetical).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "steps depend on each other: B -> E -> A -> B"
+        );
+        assert!(graph.schedule(4, letter, TieBreak::Alphabetical).is_err());
+    }
+
+    #[test]
+    fn critical_path() {
+        let graph = graph(SAMPLE);
+        let analysis = graph.critical_path(letter).unwrap();
+        assert_eq!(analysis.length(), 14);
+        assert_eq!(analysis.path, vec!["C", "F", "E"]);
+        assert!(
+            graph
+                .schedule(2, letter, TieBreak::Alphabetical)
+                .unwrap()
+                .duration()
+                >= 14
+        );
+        assert_eq!(
+            graph
+                .schedule(6, letter, TieBreak::Alphabetical)
+                .unwrap()
+                .duration(),
+            14
+        );
+        assert_eq!(
+            analysis.to_string(),
+            r"Step   Duration   Earliest   Latest   Slack
+C             3          0        0       0
+A             1          3        4       1
+F             6          3        3       0
+B             2          4        7       3
+D             4          4        5       1
+E             5          9        9       0
+critical path: C -> F -> E (14)
+"
+        );
+    }
+
+    #[test]
+    fn dot() {
+        let graph = graph(
+            r"Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step F can begin.",
+        );
+        assert_eq!(
+            graph.dot().to_string(),
+            r#"digraph steps {
+    "C" [label="C"];
+    "A" [label="A"];
+    "F" [label="F"];
+    "C" -> "A";
+    "C" -> "F";
+    "A" -> "F";
+}
+"#
+        );
+
+        let order = graph.order(TieBreak::Alphabetical).unwrap();
+        let timeline = graph.schedule(2, letter, TieBreak::Alphabetical).unwrap();
+        let dot = graph
+            .dot()
+            .with_order(&order)
+            .with_timeline(&timeline)
+            .to_string();
+        assert!(dot.contains(r#""A" [label="A\n#2\n3-4 (worker 1)"];"#));
+
+        let mut odd = TaskGraph::new();
+        odd.add_dependency(r#"say "hi""#, r"back\slash");
+        assert!(odd
+            .dot()
+            .to_string()
+            .contains(r#""say \"hi\"" -> "back\\slash";"#));
+    }
+}
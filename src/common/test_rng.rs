@@ -0,0 +1,17 @@
+/// Reproducible numbers for tests that check against a brute force
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Lcg(seed)
+    }
+
+    /// The next number in `0..bound`
+    pub fn below(&mut self, bound: u64) -> i64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1);
+        ((self.0 >> 33) % bound) as i64
+    }
+}
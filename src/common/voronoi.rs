@@ -0,0 +1,250 @@
+use std::collections::BTreeSet;
+
+/// Number of grid cells closer to a site than to any other site, ties belong to no one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Area {
+    Finite(u64),
+    Infinite,
+}
+
+impl Area {
+    pub fn finite(self) -> Option<u64> {
+        match self {
+            Area::Finite(area) => Some(area),
+            Area::Infinite => None,
+        }
+    }
+}
+
+/// Manhattan Voronoi area of every site, in the same order as `sites`.
+///
+/// A region is infinite iff it reaches the bounding box of the sites; anything outside the box is
+/// owned by whoever owns the closest cell on its edge. Inside the box only a few rows are
+/// computed: between two consecutive critical rows (a site's row, or a row where two sites start
+/// or stop shadowing each other) every site's share of a row changes by a constant amount, so
+/// each band is summed as an arithmetic series.
+pub fn areas(sites: &[(i64, i64)]) -> Vec<Area> {
+    if sites.is_empty() {
+        return vec![];
+    }
+
+    let (min_x, max_x) = bounds(sites.iter().map(|&(x, _)| x));
+    let (min_y, max_y) = bounds(sites.iter().map(|&(_, y)| y));
+    let transposed: Vec<_> = sites.iter().map(|&(x, y)| (y, x)).collect();
+    let edges = [
+        (sites, min_y, (min_x, max_x)),
+        (sites, max_y, (min_x, max_x)),
+        (&transposed[..], min_x, (min_y, max_y)),
+        (&transposed[..], max_x, (min_y, max_y)),
+    ];
+
+    let mut infinite = vec![false; sites.len()];
+    edges.iter().for_each(|&(sites, row, (lo, hi))| {
+        row_owners(sites, row)
+            .into_iter()
+            .zip(infinite.iter_mut())
+            .filter(|(owned, _)| owned.map_or(false, |(l, r)| l <= hi && r >= lo))
+            .for_each(|(_, infinite)| *infinite = true);
+    });
+
+    let rows = critical_rows(sites, min_y, max_y);
+    let counts = |row| {
+        row_owners(sites, row)
+            .into_iter()
+            .map(|owned| {
+                owned.map_or(0, |(l, r)| {
+                    i128::from((r.min(max_x) - l.max(min_x) + 1).max(0))
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut totals = vec![0_i128; sites.len()];
+    rows.iter().enumerate().for_each(|(k, &row)| {
+        totals
+            .iter_mut()
+            .zip(counts(row))
+            .for_each(|(t, c)| *t += c);
+
+        let band = rows
+            .get(k + 1)
+            .map_or(0, |&next| i128::from(next - row - 1));
+        if band > 0 {
+            let first = counts(row + 1);
+            let second = if band > 1 {
+                counts(row + 2)
+            } else {
+                first.clone()
+            };
+            totals
+                .iter_mut()
+                .zip(first.into_iter().zip(second))
+                .for_each(|(t, (a, b))| *t += band * a + (b - a) * band * (band - 1) / 2);
+        }
+    });
+
+    totals
+        .into_iter()
+        .zip(infinite)
+        .map(|(total, infinite)| {
+            if infinite {
+                Area::Infinite
+            } else {
+                Area::Finite(total as u64)
+            }
+        })
+        .collect()
+}
+
+fn bounds(values: impl Iterator<Item = i64> + Clone) -> (i64, i64) {
+    (values.clone().min().unwrap(), values.max().unwrap())
+}
+
+/// Rows within `min..=max` where the way sites split a row may change: every site's row, and
+/// every row where the vertical distances of two sites differ by exactly their horizontal one
+fn critical_rows(sites: &[(i64, i64)], min: i64, max: i64) -> Vec<i64> {
+    let mut rows: BTreeSet<_> = sites.iter().map(|&(_, y)| y).collect();
+    rows.insert(min);
+    rows.insert(max);
+    sites.iter().enumerate().for_each(|(i, &(xa, ya))| {
+        sites[i + 1..].iter().for_each(|&(xb, yb)| {
+            let dx = (xa - xb).abs();
+            for &twice in &[ya + yb + dx, ya + yb - dx] {
+                rows.insert(twice.div_euclid(2));
+                rows.insert(twice.div_euclid(2) + twice.rem_euclid(2));
+            }
+        });
+    });
+    rows.into_iter()
+        .filter(|y| (min..=max).contains(y))
+        .collect()
+}
+
+/// The inclusive columns of `row` owned by each site, unbounded columns are `i64::MIN`/`MAX`.
+///
+/// Over the row every site is a cone `|x - site| + rise`. A cone that some other cone reaches at
+/// its apex owns nothing, and if it is passed there it doesn't even tie anywhere. The cones left
+/// touch the lower envelope in apex order, so each is only bounded by its neighbours.
+fn row_owners(sites: &[(i64, i64)], row: i64) -> Vec<Option<(i64, i64)>> {
+    let mut cones: Vec<_> = sites
+        .iter()
+        .enumerate()
+        .map(|(i, &(x, y))| (x, (y - row).abs(), i))
+        .collect();
+    cones.sort_unstable();
+
+    // lowest `rise - x` before and `rise + x` after each cone, the height of the earlier and
+    // later cones at its apex once shifted by its own `x`
+    let mut before = vec![i64::MAX; cones.len()];
+    let mut after = vec![i64::MAX; cones.len()];
+    (1..cones.len()).for_each(|k| {
+        let (x, c, _) = cones[k - 1];
+        before[k] = before[k - 1].min(c - x);
+    });
+    (0..cones.len().saturating_sub(1)).rev().for_each(|k| {
+        let (x, c, _) = cones[k + 1];
+        after[k] = after[k + 1].min(c + x);
+    });
+    let envelope: Vec<_> = cones
+        .iter()
+        .enumerate()
+        .filter(|&(k, &(x, c, _))| before[k] >= c - x && after[k] >= c + x)
+        .map(|(k, &(x, c, i))| (x, c, i, before[k] > c - x && after[k] > c + x))
+        .collect();
+
+    let mut owners = vec![None; sites.len()];
+    envelope
+        .iter()
+        .enumerate()
+        .filter(|&(_, &(_, _, _, owns))| owns)
+        .for_each(|(k, &(x, c, i, _))| {
+            let left = k.checked_sub(1).map_or(i64::MIN, |p| {
+                let (xp, cp, _, _) = envelope[p];
+                (x + xp + c - cp).div_euclid(2) + 1
+            });
+            let right = envelope.get(k + 1).map_or(i64::MAX, |&(xn, cn, _, _)| {
+                -(c - cn - x - xn).div_euclid(2) - 1
+            });
+            owners[i] = Some((left, right));
+        });
+    owners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::Lcg;
+
+    fn brute_force(sites: &[(i64, i64)]) -> Vec<Area> {
+        let (min_x, max_x) = bounds(sites.iter().map(|&(x, _)| x));
+        let (min_y, max_y) = bounds(sites.iter().map(|&(_, y)| y));
+        let mut totals = vec![0; sites.len()];
+        let mut infinite = vec![false; sites.len()];
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let distances: Vec<_> = sites
+                    .iter()
+                    .map(|&(sx, sy)| (sx - x).abs() + (sy - y).abs())
+                    .collect();
+                let min = *distances.iter().min().unwrap();
+                if distances.iter().filter(|&&d| d == min).count() > 1 {
+                    continue;
+                }
+                let owner = distances.iter().position(|&d| d == min).unwrap();
+                totals[owner] += 1;
+                if x == min_x || x == max_x || y == min_y || y == max_y {
+                    infinite[owner] = true;
+                }
+            }
+        }
+        totals
+            .into_iter()
+            .zip(infinite)
+            .map(|(t, i)| if i { Area::Infinite } else { Area::Finite(t) })
+            .collect()
+    }
+
+    #[test]
+    fn sample() {
+        let sites = [(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)];
+        assert_eq!(
+            areas(&sites),
+            vec![
+                Area::Infinite,
+                Area::Infinite,
+                Area::Infinite,
+                Area::Finite(9),
+                Area::Finite(17),
+                Area::Infinite
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_grid_scan() {
+        let mut rng = Lcg::new(7);
+        let mut next = |bound| rng.below(bound);
+        for _ in 0..200 {
+            let n = 2 + next(9) as usize;
+            // a narrow grid makes shared rows, columns and even sites common
+            let side = if next(2) == 0 { 30 } else { 6 };
+            let sites: Vec<_> = (0..n).map(|_| (next(side), next(side))).collect();
+            assert_eq!(areas(&sites), brute_force(&sites), "{:?}", sites);
+
+            let far: Vec<_> = sites
+                .iter()
+                .map(|&(x, y)| (x - 4_000_000_000_000, y + 9_000_000_000))
+                .collect();
+            assert_eq!(areas(&far), areas(&sites));
+        }
+    }
+
+    #[test]
+    fn large_coordinates() {
+        let k = 1_000_000_000;
+        let sites = [(0, 0), (2 * k, 0), (-2 * k, 0), (0, 2 * k), (0, -2 * k)];
+        let areas = areas(&sites);
+        assert_eq!(areas[0], Area::Finite(((2 * k - 1) * (2 * k - 1)) as u64));
+        assert!(areas[1..].iter().all(|&a| a == Area::Infinite));
+    }
+}
@@ -0,0 +1,138 @@
+use super::extensions::IteratorExt;
+use common::voronoi::{self, Area};
+
+use std::fmt;
+
+/// The day 6 map: every cell is labelled with the letter of its closest coordinate, the
+/// coordinates themselves in uppercase, and `.` where two or more coordinates are equally close.
+/// Cells whose total distance to all coordinates is below the safe distance are drawn as `#`.
+/// Labels wrap around after `z`.
+pub struct CoordMap {
+    coords: Vec<(i64, i64)>,
+    safe_distance: Option<i64>,
+}
+
+impl CoordMap {
+    pub fn new(coords: Vec<(i64, i64)>, safe_distance: Option<i64>) -> Self {
+        CoordMap {
+            coords,
+            safe_distance,
+        }
+    }
+
+    fn closest(&self, x: i64, y: i64) -> Option<usize> {
+        self.coords
+            .iter()
+            .enumerate()
+            .uniq_min_by_key(|(_, &(cx, cy))| (cx - x).abs() + (cy - y).abs())
+            .map(|(i, _)| i)
+    }
+
+    fn is_safe(&self, x: i64, y: i64) -> bool {
+        self.safe_distance.map_or(false, |max| {
+            let total: i64 = self
+                .coords
+                .iter()
+                .map(|&(cx, cy)| (cx - x).abs() + (cy - y).abs())
+                .sum();
+            total < max
+        })
+    }
+}
+
+fn label(index: usize) -> char {
+    (b'a' + (index % 26) as u8) as char
+}
+
+impl fmt::Display for CoordMap {
+    /// One cell of margin around the coordinates, followed by a legend with each coordinate's
+    /// area or `infinite` if it reaches the edge of the coordinates' bounding box
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let min_x = self.coords.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let max_x = self.coords.iter().map(|&(x, _)| x).max().unwrap_or(0);
+        let min_y = self.coords.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let max_y = self.coords.iter().map(|&(_, y)| y).max().unwrap_or(0);
+
+        for y in min_y - 1..=max_y + 1 {
+            let row: String = (min_x - 1..=max_x + 1)
+                .map(|x| {
+                    if let Some(i) = self.coords.iter().position(|&c| c == (x, y)) {
+                        label(i).to_ascii_uppercase()
+                    } else if self.is_safe(x, y) {
+                        '#'
+                    } else {
+                        self.closest(x, y).map_or('.', label)
+                    }
+                })
+                .collect();
+            writeln!(f, "{}", row)?;
+        }
+
+        self.coords
+            .iter()
+            .zip(voronoi::areas(&self.coords))
+            .enumerate()
+            .try_for_each(|(i, (&(x, y), area))| {
+                let label = label(i).to_ascii_uppercase();
+                match area {
+                    Area::Finite(area) => writeln!(f, "{} ({}, {}): {}", label, x, y, area),
+                    Area::Infinite => writeln!(f, "{} ({}, {}): infinite", label, x, y),
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COORDS: &[(i64, i64)] = &[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)];
+
+    #[test]
+    fn closest_coordinates() {
+        let map = CoordMap::new(COORDS.to_vec(), None);
+        assert_eq!(
+            map.to_string(),
+            r"aaaaa.cccc
+aAaaa.cccc
+aaaddecccc
+aadddeccCc
+..dDdeeccc
+bb.deEeecc
+bBb.eeee..
+bbb.eeefff
+bbb.eeffff
+bbb.ffffFf
+bbb.ffffff
+A (1, 1): infinite
+B (1, 6): infinite
+C (8, 3): infinite
+D (3, 4): 9
+E (5, 5): 17
+F (8, 9): infinite
+"
+        );
+    }
+
+    #[test]
+    fn safe_region() {
+        let map = CoordMap::new(COORDS.to_vec(), Some(32));
+        let rows: Vec<_> = map.to_string().lines().take(11).map(String::from).collect();
+        assert_eq!(
+            rows,
+            vec![
+                "aaaaa.cccc",
+                "aAaaa.cccc",
+                "aaaddecccc",
+                "aad###ccCc",
+                "..#D###ccc",
+                "bb###E#ecc",
+                "bBb###ee..",
+                "bbb.eeefff",
+                "bbb.eeffff",
+                "bbb.ffffFf",
+                "bbb.ffffff",
+            ]
+        );
+    }
+}
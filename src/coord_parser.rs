@@ -1,6 +1,8 @@
 use super::extensions::*;
 
-pub fn parse(input: &str) -> Result<Vec<(u16, u16)>, std::num::ParseIntError> {
+use std::str::FromStr;
+
+pub fn parse<T: FromStr>(input: &str) -> Result<Vec<(T, T)>, T::Err> {
     input
         .trim()
         .lines()
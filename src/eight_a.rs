@@ -1,30 +1,10 @@
-fn solve(input: &str) -> usize {
-    let mut input = input
-        .split_whitespace()
-        .map(|i| i.parse::<usize>().unwrap());
+use common::{
+    input_fetcher,
+    license_tree::{LicenseNode, ParseError},
+};
 
-    let mut builder = vec![Node::Header];
-    let mut sum = 0;
-    while let Some(n) = builder.pop() {
-        match n {
-            Node::Header => {
-                let children = input.next().unwrap();
-                let metadata = input.next().unwrap();
-                builder.push(Node::Metadata(metadata));
-                builder.extend(std::iter::repeat(Node::Header).take(children));
-            }
-            Node::Metadata(count) => {
-                sum += input.by_ref().take(count).sum::<usize>();
-            }
-        }
-    }
-    sum
-}
-
-#[derive(Clone, Copy)]
-enum Node {
-    Header,
-    Metadata(usize),
+fn solve(input: &str) -> Result<usize, ParseError> {
+    Ok(input.parse::<LicenseNode>()?.metadata_sum())
 }
 
 #[cfg(test)]
@@ -34,8 +14,19 @@ mod tests {
     #[test]
     fn test() {
         let input = r"2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
-        assert_eq!(solve(input), 138);
+        assert_eq!(solve(input), Ok(138));
+    }
+}
+
+fn main() {
+    let input = input_fetcher::fetch_or_exit(8);
+    match solve(&input) {
+        Ok(answer) => println!("{}", answer),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
     }
 }
 
-common::bootstrap!(8);
+//common::bootstrap!(8);
@@ -1,60 +1,12 @@
-fn solve(input: &str) -> usize {
-    let mut input = input
-        .split_whitespace()
-        .map(|i| i.parse::<usize>().unwrap());
+use common::{
+    input_fetcher,
+    license_tree::{LicenseNode, ParseError},
+};
 
-    let mut builder = vec![Node::Header];
-    let mut adder = vec![];
-    while let Some(n) = builder.pop() {
-        match n {
-            Node::Header => {
-                let children = input.next().unwrap();
-                let metadata = input.next().unwrap();
-                builder.push(Node::Metadata(metadata));
-                builder.extend(std::iter::repeat(Node::Header).take(children));
-                if children == 0 {
-                    adder.push(NodeAdder::Metadata)
-                } else {
-                    adder.push(NodeAdder::Children(vec![]));
-                }
-            }
-            Node::Metadata(count) => {
-                let metadata = input.by_ref().take(count);
-                let value = match adder.pop().unwrap() {
-                    NodeAdder::Metadata => metadata.sum::<usize>(),
-                    NodeAdder::Children(children) => metadata
-                        .filter_map(|i| i.checked_sub(1))
-                        .filter_map(|i| children.get(i))
-                        .sum(),
-                };
-                if adder.is_empty() {
-                    return value;
-                }
-                adder
-                    .iter_mut()
-                    .rev()
-                    .find_map(|a| match a {
-                        NodeAdder::Metadata => None,
-                        NodeAdder::Children(ref mut children) => Some(children),
-                    })
-                    .unwrap()
-                    .push(value);
-            }
-        }
-    }
-    0
-}
-
-#[derive(Clone, Copy, Debug)]
-enum Node {
-    Header,
-    Metadata(usize),
-}
+use std::{env, process};
 
-#[derive(Debug)]
-enum NodeAdder {
-    Children(Vec<usize>),
-    Metadata,
+fn solve(input: &str) -> Result<usize, ParseError> {
+    Ok(input.parse::<LicenseNode>()?.value())
 }
 
 #[cfg(test)]
@@ -64,8 +16,55 @@ mod tests {
     #[test]
     fn test() {
         let input = r"2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
-        assert_eq!(solve(input), 66);
+        assert_eq!(solve(input), Ok(66));
+    }
+}
+
+fn parse_or_exit(input: &str) -> LicenseNode {
+    input.parse().unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    })
+}
+
+/// Instead of the answer `--tree` prints every node with its value, `--path 1/3/2` prints the
+/// value of the node at that child path and `--dangling` lists metadata entries pointing to no
+/// child
+fn main() {
+    let input = input_fetcher::fetch_or_exit(8);
+    let args: Vec<_> = env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["--tree"] => print!("{}", parse_or_exit(&input).outline()),
+        ["--dangling"] => parse_or_exit(&input)
+            .dangling_references()
+            .iter()
+            .for_each(|d| println!("{}", d)),
+        ["--path", path] => {
+            let indices: Result<Vec<usize>, _> = path
+                .split('/')
+                .filter(|i| !i.is_empty())
+                .map(str::parse)
+                .collect();
+            let tree = parse_or_exit(&input);
+            match indices
+                .ok()
+                .and_then(|i| tree.node_at(&i).map(LicenseNode::value))
+            {
+                Some(value) => println!("{}", value),
+                None => {
+                    eprintln!("error: no node at '{}'", path);
+                    process::exit(1);
+                }
+            }
+        }
+        _ => match solve(&input) {
+            Ok(answer) => println!("{}", answer),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                process::exit(1);
+            }
+        },
     }
 }
 
-common::bootstrap!(8);
+//common::bootstrap!(8);
@@ -1,12 +1,23 @@
 #![feature(never_type)]
 
+mod acre;
+
 use std::str::FromStr;
 
+use self::acre::Acre;
+use common::frame_render::{Canvas, Error, Recorder};
+
 fn solve(input: &str) -> usize {
+    solve_recorded(input, Recorder::disabled()).expect("a disabled recorder writes nothing")
+}
+
+fn solve_recorded(input: &str, mut frames: Recorder) -> Result<usize, Error> {
     let mut world: World = input.parse().unwrap();
-    (0..10).for_each(|_| {
+    frames.record(|| world.canvas())?;
+    (0..10).try_for_each(|_| {
         world.tick();
-    });
+        frames.record(|| world.canvas())
+    })?;
     let (trees, lumbers) = world
         .acres
         .into_iter()
@@ -15,7 +26,7 @@ fn solve(input: &str) -> usize {
             Acre::Trees => (lumbers, trees + 1),
             Acre::Ground => (lumbers, trees),
         });
-    trees * lumbers
+    Ok(trees * lumbers)
 }
 
 #[derive(Debug)]
@@ -37,6 +48,10 @@ impl World {
             .collect();
     }
 
+    fn canvas(&self) -> Canvas {
+        Canvas::from_tiles(&self.acres, self.cols)
+    }
+
     fn neighbors(&self, index: usize) -> [Option<Acre>; 8] {
         let up_i = index.checked_sub(self.cols);
         let down_i = if index + self.cols < self.acres.len() {
@@ -71,54 +86,6 @@ impl World {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Acre {
-    Ground,
-    Trees,
-    Lumberyard,
-}
-
-impl Acre {
-    fn tick(self, neighbors: impl Iterator<Item = Acre>) -> Acre {
-        match self {
-            Acre::Ground => {
-                if neighbors.filter(|a| *a == Acre::Trees).count() >= 3 {
-                    Acre::Trees
-                } else {
-                    self
-                }
-            }
-
-            Acre::Trees => {
-                if neighbors.filter(|a| *a == Acre::Lumberyard).count() >= 3 {
-                    Acre::Lumberyard
-                } else {
-                    self
-                }
-            }
-            Acre::Lumberyard => {
-                let mut has_trees = false;
-                let mut has_lumbers = false;
-                for n in neighbors {
-                    match n {
-                        Acre::Lumberyard => has_lumbers = true,
-                        Acre::Trees => has_trees = true,
-                        _ => continue,
-                    }
-                    if has_trees && has_lumbers {
-                        break;
-                    }
-                }
-                if !(has_trees && has_lumbers) {
-                    Acre::Ground
-                } else {
-                    self
-                }
-            }
-        }
-    }
-}
-
 impl FromStr for World {
     type Err = !;
 
@@ -159,7 +126,7 @@ mod tests {
     }
 }
 
-common::read_main!();
+common::record_main!();
 //common::bootstrap!(17);
 
 use std::fmt;
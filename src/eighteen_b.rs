@@ -1,9 +1,19 @@
 #![feature(never_type)]
 
+mod acre;
+
 use std::str::FromStr;
 
+use self::acre::Acre;
+use common::frame_render::{Canvas, Error, Recorder};
+
 fn solve(input: &str) -> usize {
+    solve_recorded(input, Recorder::disabled()).expect("a disabled recorder writes nothing")
+}
+
+fn solve_recorded(input: &str, mut frames: Recorder) -> Result<usize, Error> {
     let mut world: World = input.parse().unwrap();
+    frames.record(|| world.canvas())?;
     let mut old_worlds = Vec::with_capacity(500);
     let mut cycle: Option<(usize, usize)> = None;
     let last_minute: usize = 1_000_000_000;
@@ -11,6 +21,7 @@ fn solve(input: &str) -> usize {
     for _ in 0..last_minute {
         let new_world = world.tick();
         let old_world = std::mem::replace(&mut world, new_world);
+        frames.record(|| world.canvas())?;
 
         old_worlds.push(old_world);
         if let Some(i) = old_worlds.iter().position(|w| *w == world) {
@@ -32,7 +43,7 @@ fn solve(input: &str) -> usize {
             Acre::Trees => (lumbers, trees + 1),
             Acre::Ground => (lumbers, trees),
         });
-    trees * lumbers
+    Ok(trees * lumbers)
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -58,6 +69,10 @@ impl World {
         }
     }
 
+    fn canvas(&self) -> Canvas {
+        Canvas::from_tiles(&self.acres, self.cols)
+    }
+
     fn neighbors(&self, index: usize) -> [Option<Acre>; 8] {
         let up_i = index.checked_sub(self.cols);
         let down_i = if index + self.cols < self.acres.len() {
@@ -92,54 +107,6 @@ impl World {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Acre {
-    Ground,
-    Trees,
-    Lumberyard,
-}
-
-impl Acre {
-    fn tick(self, neighbors: impl Iterator<Item = Acre>) -> Acre {
-        match self {
-            Acre::Ground => {
-                if neighbors.filter(|a| *a == Acre::Trees).count() >= 3 {
-                    Acre::Trees
-                } else {
-                    self
-                }
-            }
-
-            Acre::Trees => {
-                if neighbors.filter(|a| *a == Acre::Lumberyard).count() >= 3 {
-                    Acre::Lumberyard
-                } else {
-                    self
-                }
-            }
-            Acre::Lumberyard => {
-                let mut has_trees = false;
-                let mut has_lumbers = false;
-                for n in neighbors {
-                    match n {
-                        Acre::Lumberyard => has_lumbers = true,
-                        Acre::Trees => has_trees = true,
-                        _ => continue,
-                    }
-                    if has_trees && has_lumbers {
-                        break;
-                    }
-                }
-                if !(has_trees && has_lumbers) {
-                    Acre::Ground
-                } else {
-                    self
-                }
-            }
-        }
-    }
-}
-
 impl FromStr for World {
     type Err = !;
 
@@ -160,7 +127,7 @@ impl FromStr for World {
     }
 }
 
-common::read_main!();
+common::record_main!();
 //common::bootstrap!(17);
 
 use std::fmt;
@@ -1,23 +1,34 @@
 #![feature(never_type)]
 #![feature(bind_by_move_pattern_guards)]
 
+mod battle_tile;
+
 use std::{
     collections::{HashSet, VecDeque},
     fmt,
     str::FromStr,
 };
 
+use self::battle_tile::{Tile, Unit, UnitKind};
+use common::frame_render::{Canvas, Error, Recorder};
+
 fn solve(input: &str) -> u32 {
+    solve_recorded(input, Recorder::disabled()).expect("a disabled recorder writes nothing")
+}
+
+fn solve_recorded(input: &str, mut frames: Recorder) -> Result<u32, Error> {
     let mut field: BattleField = input.parse().unwrap();
     let mut rounds = 0;
     loop {
+        frames.record(|| field.canvas())?;
         if field.round() {
             rounds += 1;
         } else {
             break;
         }
     }
-    rounds * field.health()
+    frames.record(|| field.canvas())?;
+    Ok(rounds * field.health())
 }
 
 struct BattleField {
@@ -76,6 +87,10 @@ impl BattleField {
         true
     }
 
+    fn canvas(&self) -> Canvas {
+        Canvas::from_tiles(&self.tiles, self.num_cols)
+    }
+
     fn health(&self) -> u32 {
         self.tiles
             .iter()
@@ -195,36 +210,6 @@ impl BattleField {
     }
 }
 
-#[derive(Debug)]
-enum Tile {
-    Open,
-    Wall,
-    Unit(Unit),
-}
-
-#[derive(Debug, Clone, Copy)]
-struct Unit {
-    health: u8,
-    id: usize,
-    kind: UnitKind,
-}
-
-impl Unit {
-    fn is_enemy(&self, other: &Self) -> bool {
-        match (&self.kind, &other.kind) {
-            (UnitKind::Goblin, UnitKind::Elf) => true,
-            (UnitKind::Elf, UnitKind::Goblin) => true,
-            _ => false,
-        }
-    }
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum UnitKind {
-    Goblin,
-    Elf,
-}
-
 impl Tile {
     fn is_elf(&self) -> bool {
         match self {
@@ -232,13 +217,6 @@ impl Tile {
             _ => false,
         }
     }
-
-    fn is_goblin(&self) -> bool {
-        match self {
-            Tile::Unit(u) => u.kind == UnitKind::Goblin,
-            _ => false,
-        }
-    }
 }
 
 impl FromStr for BattleField {
@@ -371,5 +349,5 @@ impl fmt::Display for BattleField {
     }
 }
 
-common::read_main!();
+common::record_main!();
 //common::bootstrap!(15);
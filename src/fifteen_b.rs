@@ -1,22 +1,32 @@
 #![feature(never_type)]
 #![feature(bind_by_move_pattern_guards)]
 
+mod battle_tile;
+
 use std::{
     collections::{HashSet, VecDeque},
     fmt,
     str::FromStr,
 };
 
+use self::battle_tile::{Tile, Unit, UnitKind};
+use common::frame_render::{Canvas, Error, Recorder};
+
 fn solve(input: &str) -> u32 {
+    solve_recorded(input, Recorder::disabled()).expect("a disabled recorder writes nothing")
+}
+
+fn solve_recorded(input: &str, mut frames: Recorder) -> Result<u32, Error> {
     let original: BattleField = input.parse().unwrap();
     for elf_damage in 3.. {
         let mut field = original.clone();
         let mut rounds = 0;
         loop {
+            frames.record(|| field.canvas())?;
             match field.round(elf_damage) {
                 BattleState::Continue => rounds += 1,
                 BattleState::Lost => break,
-                BattleState::Won => return rounds * field.health(),
+                BattleState::Won => return Ok(rounds * field.health()),
             }
         }
     }
@@ -89,6 +99,10 @@ impl BattleField {
         BattleState::Continue
     }
 
+    fn canvas(&self) -> Canvas {
+        Canvas::from_tiles(&self.tiles, self.num_cols)
+    }
+
     fn health(&self) -> u32 {
         self.tiles
             .iter()
@@ -208,45 +222,6 @@ impl BattleField {
     }
 }
 
-#[derive(Debug, Clone)]
-enum Tile {
-    Open,
-    Wall,
-    Unit(Unit),
-}
-
-#[derive(Debug, Clone, Copy)]
-struct Unit {
-    health: u8,
-    id: usize,
-    kind: UnitKind,
-}
-
-impl Unit {
-    fn is_enemy(&self, other: &Self) -> bool {
-        match (&self.kind, &other.kind) {
-            (UnitKind::Goblin, UnitKind::Elf) => true,
-            (UnitKind::Elf, UnitKind::Goblin) => true,
-            _ => false,
-        }
-    }
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum UnitKind {
-    Goblin,
-    Elf,
-}
-
-impl Tile {
-    fn is_goblin(&self) -> bool {
-        match self {
-            Tile::Unit(u) => u.kind == UnitKind::Goblin,
-            _ => false,
-        }
-    }
-}
-
 impl FromStr for BattleField {
     type Err = !;
 
@@ -365,5 +340,5 @@ impl fmt::Display for BattleField {
     }
 }
 
-common::read_main!();
+common::record_main!();
 //common::bootstrap!(15);
@@ -1,6 +1,4 @@
-mod polymer_len;
-
-use self::polymer_len::polymer_len;
+use common::polymer::polymer_len;
 
 fn solve(input: &str) -> usize {
     polymer_len(input.trim().as_bytes())
@@ -1,24 +1,57 @@
-mod polymer_len;
+use common::polymer::{ascii_polarity, reduce};
+use rayon::prelude::*;
 
-use self::polymer_len::polymer_len;
+use std::fmt;
 
-fn solve(input: &str) -> usize {
-    let input = input.trim();
+fn solve(input: &str) -> Improvements {
+    // removing a unit type never stops other units from reacting, so the fully reacted polymer
+    // from part one reduces to the same result as the raw input and is usually much shorter
+    let reduced = reduce(input.trim().bytes(), ascii_polarity);
 
-    (b'a'..=b'z')
-        .map(|e| {
-            input
-                .bytes()
-                .filter(|c| c.to_ascii_lowercase() != e)
-                .collect::<Vec<_>>()
+    let letters: Vec<_> = (b'a'..=b'z').collect();
+    let lengths = letters
+        .par_iter()
+        .map(|&letter| {
+            let units = reduced.iter().cloned();
+            let len = reduce(
+                units.filter(|c| c.to_ascii_lowercase() != letter),
+                ascii_polarity,
+            )
+            .len();
+            (letter as char, len)
         })
-        .map(|s| polymer_len(&s))
-        .min()
-        .unwrap()
+        .collect();
+
+    Improvements { lengths }
+}
+
+/// Length of the reacted polymer after removing each unit type
+struct Improvements {
+    lengths: Vec<(char, usize)>,
+}
+
+impl Improvements {
+    fn shortest(&self) -> (char, usize) {
+        self.lengths
+            .iter()
+            .cloned()
+            .min_by_key(|&(_, len)| len)
+            .unwrap()
+    }
 }
 
 common::bootstrap!(5);
 
+impl fmt::Display for Improvements {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.lengths
+            .iter()
+            .try_for_each(|(letter, len)| writeln!(f, "{}: {}", letter, len))?;
+        let (letter, len) = self.shortest();
+        write!(f, "shortest: {} (removing {})", len, letter)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -26,6 +59,11 @@ mod test {
     #[test]
     fn test() {
         let input = "dabAcCaCBAcCcaDA\n";
-        assert_eq!(solve(input), 4);
+        let improvements = solve(input);
+        assert_eq!(improvements.shortest(), ('c', 4));
+        assert_eq!(
+            improvements.lengths[..5],
+            [('a', 6), ('b', 8), ('c', 4), ('d', 6), ('e', 10)]
+        );
     }
 }
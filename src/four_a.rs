@@ -1,42 +1,20 @@
-#![feature(try_trait)]
+use common::guard_schedule::*;
 
-mod guard_schedule;
+use std::convert::TryFrom;
 
-use self::guard_schedule::*;
-
-use std::{collections::HashMap, convert::TryFrom};
-
-fn solve(input: &str) -> u16 {
+/// `None` when no guard ever falls asleep
+fn solve(input: &str) -> Option<u16> {
     let mut entries: Vec<_> = input.lines().map(|l| Entry::try_from(l).unwrap()).collect();
-    entries.sort_unstable_by(|a, b| a.date().cmp(b.date()));
+    entries.sort_unstable_by_key(Entry::timestamp);
     let guards = Schedule::guard_schedules(&entries).unwrap();
 
-    let (id, schedules) = guards
-        .iter()
-        .max_by_key(|(_, schedules)| {
-            schedules
-                .iter()
-                .flat_map(|s| s.minutes.iter())
-                .filter(|&&s| s == GuardState::Asleep)
-                .count()
-        })
-        .unwrap();
-
-    let minute = schedules
-        .iter()
-        .flat_map(Schedule::asleep_minutes)
-        .fold(HashMap::<usize, u16>::new(), |mut acc, m| {
-            let count = acc.entry(m).or_default();
-            *count += 1;
-            acc
-        })
-        .iter()
-        .max_by_key(|(_, &c)| c)
-        .map(|(m, _)| m)
-        .cloned()
-        .unwrap();
+    let stats = GuardStats::all(&guards)
+        .into_iter()
+        .filter(|s| s.total_asleep > 0)
+        .max_by_key(|s| s.total_asleep)?;
+    let (minutes, _) = stats.sleepiest_minutes();
 
-    id * minute as u16
+    minutes.first().map(|&m| stats.guard * m as u16)
 }
 
 #[cfg(test)]
@@ -63,8 +41,26 @@ mod test {
 [1518-11-05 00:45] falls asleep
 [1518-11-05 00:55] wakes up";
 
-        assert_eq!(solve(input), 240);
+        assert_eq!(solve(input), Some(240));
+    }
+
+    #[test]
+    fn never_asleep() {
+        let input = r"[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-02 00:00] Guard #99 begins shift";
+
+        assert_eq!(solve(input), None);
+        assert_eq!(solve(""), None);
     }
 }
 
-common::bootstrap!(4);
+fn main() {
+    let input = common::input_fetcher::fetch_or_exit(4);
+    match solve(input.as_str()) {
+        Some(answer) => println!("{}", answer),
+        None => {
+            eprintln!("error: no guard ever falls asleep");
+            std::process::exit(1);
+        }
+    }
+}
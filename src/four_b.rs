@@ -1,33 +1,15 @@
-#![feature(try_trait)]
+use common::guard_schedule::*;
 
-mod guard_schedule;
-
-use self::guard_schedule::*;
-use std::{collections::HashMap, convert::TryFrom};
-
-fn solve(input: &str) -> u32 {
-    let mut entries: Vec<_> = input.lines().map(|l| Entry::try_from(l).unwrap()).collect();
-    entries.sort_unstable_by(|a, b| a.date().cmp(b.date()));
-
-    Schedule::guard_schedules(&entries)
-        .unwrap()
+/// `None` when no guard ever falls asleep
+fn solve(guards: &Guards) -> Option<u32> {
+    GuardStats::all(guards)
         .into_iter()
-        .filter_map(|(id, schedules)| {
-            schedules
-                .iter()
-                .flat_map(Schedule::asleep_minutes)
-                .fold(HashMap::<usize, u16>::new(), |mut acc, m| {
-                    *(acc.entry(m).or_default()) += 1;
-                    acc
-                })
-                .iter()
-                .max_by_key(|(_, &c)| c)
-                .map(|(&a, &b)| (a, b))
-                .map(|t| (id, t))
+        .filter_map(|s| {
+            let (minutes, count) = s.sleepiest_minutes();
+            minutes.first().map(|&m| (s.guard, m, count))
         })
-        .max_by_key(|(_, (_, count))| *count)
-        .map(|(id, (minute, _))| u32::from(id) * minute as u32)
-        .unwrap()
+        .max_by_key(|&(_, _, count)| count)
+        .map(|(id, minute, _)| u32::from(id) * minute as u32)
 }
 
 #[cfg(test)]
@@ -54,18 +36,65 @@ mod test {
 [1518-11-05 00:45] falls asleep
 [1518-11-05 00:55] wakes up";
 
-        assert_eq!(solve(input), 4455);
+        let mut log = GuardLog::new();
+        log.add("input", input);
+        let (guards, _) = log.schedules().unwrap();
+        assert_eq!(solve(&guards), Some(4455));
+    }
+
+    #[test]
+    fn never_asleep() {
+        let mut log = GuardLog::new();
+        log.add("input", "[1518-11-01 00:00] Guard #10 begins shift");
+        let (guards, _) = log.schedules().unwrap();
+        assert_eq!(solve(&guards), None);
     }
 }
 
-use std::io::{self, Read};
+use std::{
+    env, fs,
+    io::{self, Read},
+    process,
+};
 
+/// Usage: `four_b [--report] [LOG...]`, logs are merged in the order given and stdin is read when
+/// there are none. `--report` prints the puzzle chart of every shift instead of the answer.
 fn main() {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input).unwrap();
+    let (flags, paths): (Vec<_>, Vec<_>) = env::args().skip(1).partition(|a| a.starts_with("--"));
 
-    let output = solve(&input);
-    println!("{}", output);
+    let mut log = GuardLog::new();
+    if paths.is_empty() {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).unwrap();
+        log.add("<stdin>", &input);
+    }
+    for path in &paths {
+        match fs::read_to_string(path) {
+            Ok(input) => log.add(path, &input),
+            Err(e) => {
+                eprintln!("error: {}: {}", path, e);
+                process::exit(1);
+            }
+        }
+    }
+
+    let (guards, warnings) = log.schedules().unwrap_or_else(|errors| {
+        errors.iter().for_each(|e| eprintln!("error: {}", e));
+        process::exit(1);
+    });
+    warnings.iter().for_each(|w| eprintln!("warning: {}", w));
+
+    if flags.iter().any(|f| f == "--report") {
+        print!("{}", Report::new(&guards));
+    } else {
+        match solve(&guards) {
+            Some(answer) => println!("{}", answer),
+            None => {
+                eprintln!("error: no guard ever falls asleep");
+                process::exit(1);
+            }
+        }
+    }
 }
 
 //common::bootstrap!(4);
@@ -0,0 +1,110 @@
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+};
+
+#[derive(Debug)]
+pub struct Drift {
+    /// frequency reached before applying the change at the same index
+    partial_sums: Vec<i64>,
+    total: i64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Repeat {
+    pub frequency: i64,
+    /// 0-based pass over the list of changes in which the repeat happened
+    pub iteration: usize,
+    /// index of the change that produced the repeated frequency
+    pub index: usize,
+}
+
+impl Drift {
+    pub fn new(changes: impl IntoIterator<Item = i64>) -> Self {
+        let mut total = 0;
+        let partial_sums = changes
+            .into_iter()
+            .map(|c| {
+                let sum = total;
+                total += c;
+                sum
+            })
+            .collect();
+        Drift {
+            partial_sums,
+            total,
+        }
+    }
+
+    /// Returns `None` when the frequencies never repeat no matter how many times the changes are
+    /// cycled, or only after more changes than fit in a `usize`
+    pub fn first_repeat(&self) -> Option<Repeat> {
+        let len = self.partial_sums.len();
+        if len == 0 {
+            return None;
+        }
+
+        // a repeat inside the first pass always comes before any found by cycling
+        let mut seen = HashSet::new();
+        let first_pass = self
+            .partial_sums
+            .iter()
+            .chain(std::iter::once(&self.total))
+            .position(|&s| !seen.insert(s));
+        if let Some(applied) = first_pass {
+            return Some(self.repeat_at(applied));
+        }
+
+        if self.total == 0 {
+            return None;
+        }
+
+        // every pass shifts all partial sums by `total`, so only sums in the same residue class
+        // can ever meet: `s_j + k * total == s_i` for the smallest `k > 0`. Sums are widened as
+        // two of them can be further apart than fits in an i64.
+        let total = i128::from(self.total);
+        let mut groups: HashMap<i128, Vec<(i128, usize)>> = HashMap::new();
+        self.partial_sums.iter().enumerate().for_each(|(j, &s)| {
+            let s = i128::from(s);
+            groups.entry(s.rem_euclid(total)).or_default().push((s, j));
+        });
+
+        groups
+            .values_mut()
+            .flat_map(|group| {
+                group.sort_unstable();
+                if self.total < 0 {
+                    group.reverse();
+                }
+                group
+                    .windows(2)
+                    .filter_map(|w| {
+                        let ((from, j), (to, _)) = (w[0], w[1]);
+                        let passes = usize::try_from((to - from) / total).ok()?;
+                        passes.checked_mul(len)?.checked_add(j)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .min()
+            .map(|applied| self.repeat_at(applied))
+    }
+
+    /// Frequency after `applied` changes have been made, cycling through the list. Only called
+    /// for repeats, which are equal to a partial sum and so fit in an i64.
+    fn frequency_at(&self, applied: usize) -> i64 {
+        let len = self.partial_sums.len();
+        let passes = (applied / len) as i128;
+        let index = applied % len;
+        let frequency = i128::from(self.partial_sums[index]) + passes * i128::from(self.total);
+        i64::try_from(frequency).unwrap()
+    }
+
+    fn repeat_at(&self, applied: usize) -> Repeat {
+        let len = self.partial_sums.len();
+        Repeat {
+            frequency: self.frequency_at(applied),
+            iteration: (applied - 1) / len,
+            index: (applied - 1) % len,
+        }
+    }
+}
@@ -1,9 +1,9 @@
-use common::{bootstrap, sanitize};
+use common::calibration::{self, Error};
 
-bootstrap!(1);
+use std::io::{self, BufRead};
 
-fn solve(input: &str) -> i32 {
-    sanitize::to_i32s(input).sum()
+fn solve(input: impl BufRead) -> Result<i64, Error> {
+    calibration::frequency(input)
 }
 
 #[cfg(test)]
@@ -17,7 +17,9 @@ mod test {
                 r#"+1
 +1
 +1"#
-            ),
+                .as_bytes()
+            )
+            .unwrap(),
             3
         );
 
@@ -26,7 +28,9 @@ mod test {
                 r#"+1
 +1
 -2"#
-            ),
+                .as_bytes()
+            )
+            .unwrap(),
             0
         );
 
@@ -35,8 +39,36 @@ mod test {
                 r#"-1
 -2
 -3"#
-            ),
+                .as_bytes()
+            )
+            .unwrap(),
             -6
         );
     }
+
+    #[test]
+    fn errors() {
+        match solve("+1\n\n+2\nabc\n+3".as_bytes()) {
+            Err(Error::Malformed { line: 4, .. }) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+
+        match solve(format!("{}\n+1", i64::max_value()).as_bytes()) {
+            Err(Error::Overflow { line: 2 }) => {}
+            r => panic!("unexpected {:?}", r),
+        }
+    }
 }
+
+fn main() {
+    let stdin = io::stdin();
+    match solve(stdin.lock()) {
+        Ok(frequency) => println!("{}", frequency),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+//common::bootstrap!(1);
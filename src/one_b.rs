@@ -1,40 +1,13 @@
-use common::{bootstrap, sanitize};
+mod frequency_drift;
 
-use std::collections::HashSet;
+use self::frequency_drift::*;
+use common::calibration::{self, Error};
 
-bootstrap!(1);
+use std::io::{self, BufRead};
 
-fn solve(input: &str) -> i32 {
-    sanitize::to_i32s(input)
-        .cycle()
-        .scan(State::default(), |state, x| {
-            if state.is_done() {
-                None
-            } else {
-                state.add_number(x);
-                Some(state.sum)
-            }
-        })
-        .last()
-        .unwrap()
-}
-
-#[derive(Default, Debug)]
-struct State {
-    partial_sums: HashSet<i32>,
-    sum: i32,
-}
-
-impl State {
-    fn is_done(&self) -> bool {
-        self.partial_sums.contains(&self.sum)
-    }
-
-    fn add_number(&mut self, num: i32) {
-        let sum = self.sum + num;
-        self.partial_sums.insert(self.sum);
-        self.sum = sum;
-    }
+fn solve(input: impl BufRead) -> Result<Option<i64>, Error> {
+    let changes = calibration::collect_changes(input)?;
+    Ok(Drift::new(changes).first_repeat().map(|r| r.frequency))
 }
 
 #[cfg(test)]
@@ -47,8 +20,10 @@ mod tests {
             solve(
                 r#"+1
 -1"#
-            ),
-            0
+                .as_bytes()
+            )
+            .unwrap(),
+            Some(0)
         );
 
         assert_eq!(
@@ -58,8 +33,10 @@ mod tests {
 +4
 -2
 -4"#
-            ),
-            10
+                .as_bytes()
+            )
+            .unwrap(),
+            Some(10)
         );
 
         assert_eq!(
@@ -69,8 +46,10 @@ mod tests {
 +8
 +5
 -6"#
-            ),
-            5
+                .as_bytes()
+            )
+            .unwrap(),
+            Some(5)
         );
 
         assert_eq!(
@@ -80,8 +59,74 @@ mod tests {
 -2
 -7
 -4"#
-            ),
-            14
+                .as_bytes()
+            )
+            .unwrap(),
+            Some(14)
+        );
+    }
+
+    #[test]
+    fn repeat_position() {
+        let drift = Drift::new(vec![3, 3, 4, -2, -4]);
+        assert_eq!(
+            drift.first_repeat(),
+            Some(Repeat {
+                frequency: 10,
+                iteration: 1,
+                index: 1,
+            })
+        );
+
+        let drift = Drift::new(vec![1, -1]);
+        assert_eq!(
+            drift.first_repeat(),
+            Some(Repeat {
+                frequency: 0,
+                iteration: 0,
+                index: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn never_repeats() {
+        assert_eq!(Drift::new(vec![1, 1, 1]).first_repeat(), None);
+        assert_eq!(Drift::new(vec![-5, 2]).first_repeat(), None);
+        assert_eq!(Drift::new(vec![]).first_repeat(), None);
+    }
+
+    #[test]
+    fn distant_partial_sums() {
+        // the partial sums -2^62 and 2^62 + 1 are more than i64::MAX apart
+        let input = r#"-4611686018427387904
++4611686018427387905
++4611686018427387904
+-4611686018427387902"#;
+        assert_eq!(solve(input.as_bytes()).unwrap(), Some(4611686018427387905));
+
+        let changes = input.lines().map(|l| l.parse().unwrap());
+        assert_eq!(
+            Drift::new(changes).first_repeat(),
+            Some(Repeat {
+                frequency: 4611686018427387905,
+                iteration: 3074457345618258603,
+                index: 0,
+            })
         );
     }
 }
+
+fn main() {
+    let stdin = io::stdin();
+    match solve(stdin.lock()) {
+        Ok(Some(frequency)) => println!("{}", frequency),
+        Ok(None) => println!("frequency never repeats"),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+//common::bootstrap!(1);
@@ -1,63 +1,16 @@
-#![feature(never_type)]
-
-use std::{
-    collections::{HashMap, HashSet},
-    str,
+use common::{
+    input_fetcher,
+    task_graph::{TaskGraph, TieBreak},
 };
 
-fn solve(input: &str) -> String {
-    let graph: [Option<HashSet<u8>>; 26] = Default::default();
-    let mut graph = input
-        .lines()
-        .map(|l| l.parse::<Dependency>().unwrap())
-        .fold(graph, |mut graph, dep| {
-            graph[(dep.blocker - b'A') as usize].get_or_insert_with(Default::default);
-            graph[(dep.dependent - b'A') as usize]
-                .get_or_insert_with(HashSet::default)
-                .insert(dep.blocker);
-            graph
-        });
-
-    let mut graph: HashMap<_, _> = graph
-        .iter_mut()
-        .enumerate()
-        .filter_map(|(i, v)| v.as_mut().map(|v| (i as u8 + b'A', v)))
-        .collect();
-
-    let mut route = "".to_owned();
-
-    while !graph.is_empty() {
-        let (&val, _) = graph
-            .iter()
-            .filter(|(_, blockers)| blockers.is_empty())
-            .min_by_key(|(&i, _)| i)
-            .unwrap();
-        graph.remove(&val);
-        graph.values_mut().for_each(|blockers| {
-            blockers.remove(&val);
-        });
-        route.push(val as char)
-    }
-
-    route
-}
+use std::env;
 
-struct Dependency {
-    blocker: u8,
-    dependent: u8,
+fn solve(input: &str) -> String {
+    graph(input).order(TieBreak::Alphabetical).unwrap().concat()
 }
 
-impl str::FromStr for Dependency {
-    type Err = !;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let input = input.as_bytes();
-
-        let blocker = input[5];
-        let dependent = input[36];
-
-        Ok(Dependency { blocker, dependent })
-    }
+fn graph(input: &str) -> TaskGraph {
+    TaskGraph::from_dependencies(input.trim().lines().map(|l| l.parse().unwrap()))
 }
 
 #[cfg(test)]
@@ -78,4 +31,16 @@ Step F must be finished before step E can begin.";
     }
 }
 
-common::bootstrap!(7);
+/// `--dot` prints the steps as a Graphviz graph labelled with their order instead of the answer
+fn main() {
+    let input = input_fetcher::fetch_or_exit(7);
+    if env::args().any(|a| a == "--dot") {
+        let graph = graph(&input);
+        let order = graph.order(TieBreak::Alphabetical).unwrap();
+        print!("{}", graph.dot().with_order(&order));
+    } else {
+        println!("{}", solve(&input));
+    }
+}
+
+//common::bootstrap!(7);
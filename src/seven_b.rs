@@ -1,88 +1,62 @@
-#![feature(never_type)]
-
-use std::{
-    collections::{HashMap, HashSet},
-    str,
+use common::{
+    input_fetcher,
+    task_graph::{Dependency, DependencyParseError, TaskGraph, TieBreak, Timeline},
 };
 
-fn solve(input: &str) -> u16 {
-    duration(input, 5, 60)
+use std::{env, process};
+
+const WORKERS: usize = 5;
+const BASE_DURATION: u64 = 60;
+
+fn solve(graph: &TaskGraph) -> u64 {
+    schedule(graph, WORKERS, BASE_DURATION).duration()
 }
 
-fn duration(input: &str, num_workers: u8, base_duration: u8) -> u16 {
-    let graph: [Option<HashSet<u8>>; 26] = Default::default();
-    let mut graph = input
+/// Steps have to be named by a single uppercase letter for their duration to be known
+fn graph(input: &str) -> Result<TaskGraph, String> {
+    let dependencies = input
         .trim()
         .lines()
-        .map(|l| l.parse::<Dependency>().unwrap())
-        .fold(graph, |mut graph, dep| {
-            graph[(dep.blocker - b'A') as usize].get_or_insert_with(Default::default);
-            graph[(dep.dependent - b'A') as usize]
-                .get_or_insert_with(HashSet::default)
-                .insert(dep.blocker);
-            graph
-        });
-
-    let mut unworked: HashMap<_, _> = graph
-        .iter_mut()
-        .enumerate()
-        .filter_map(|(i, v)| v.as_mut().map(|v| (i as u8 + b'A', v)))
-        .collect();
-
-    let mut working: HashMap<u8, u16> = HashMap::new();
-    let mut duration: u16 = 0;
-
-    while !unworked.is_empty() {
-        let free_workers = 1 + num_workers as usize - working.len();
-        let mut workable: Vec<_> = unworked
-            .iter()
-            .filter_map(|(&i, blockers)| if blockers.is_empty() { Some(i) } else { None })
-            .collect();
-        workable.sort_unstable();
-        workable.iter().take(free_workers).for_each(|&i| {
-            unworked.remove(&i);
-            working.insert(i, u16::from(1 + base_duration + i - b'A'));
-        });
-
-        let (_, &work_step) = working
-            .iter()
-            .min_by_key(|(_, work_left)| *work_left)
-            .unwrap();
-        working
-            .iter_mut()
-            .for_each(|(_, work_left)| *work_left -= work_step);
-        duration += work_step as u16;
-        working.retain(|i, work_left| {
-            if *work_left == 0 {
-                unworked.values_mut().for_each(|blockers| {
-                    blockers.remove(&i);
-                });
-                false
-            } else {
-                true
+        .map(|l| {
+            let dependency: Dependency =
+                l.parse().map_err(|e: DependencyParseError| e.to_string())?;
+            match [&dependency.blocker, &dependency.dependent]
+                .iter()
+                .find(|step| letter_position(step).is_none())
+            {
+                Some(step) => Err(format!("step '{}' is not a single uppercase letter", step)),
+                None => Ok(dependency),
             }
-        });
-    }
-
-    duration + working.iter().map(|(_, &s)| s).sum::<u16>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(TaskGraph::from_dependencies(dependencies))
 }
 
-struct Dependency {
-    blocker: u8,
-    dependent: u8,
+/// `A` is 1, `Z` is 26
+fn letter_position(step: &str) -> Option<u64> {
+    match step.as_bytes() {
+        &[letter] => letter
+            .checked_sub(b'A')
+            .filter(|&p| p < 26)
+            .map(|p| u64::from(p) + 1),
+        _ => None,
+    }
 }
 
-impl str::FromStr for Dependency {
-    type Err = !;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let input = input.as_bytes();
-
-        let blocker = input[5];
-        let dependent = input[36];
+/// Steps take `base_duration` plus their letter's position in the alphabet, steps that aren't
+/// a letter are rejected by `graph`
+fn step_duration(base_duration: u64) -> impl Fn(&str) -> u64 {
+    move |step| base_duration + letter_position(step).unwrap_or(0)
+}
 
-        Ok(Dependency { blocker, dependent })
-    }
+fn schedule(graph: &TaskGraph, workers: usize, base_duration: u64) -> Timeline {
+    graph
+        .schedule(
+            workers,
+            step_duration(base_duration),
+            TieBreak::Alphabetical,
+        )
+        .unwrap()
 }
 
 #[cfg(test)]
@@ -99,10 +73,62 @@ Step B must be finished before step E can begin.
 Step D must be finished before step E can begin.
 Step F must be finished before step E can begin.";
 
-        assert_eq!(duration(input, 1, 0), 15);
+        let graph = graph(input).unwrap();
+        assert_eq!(schedule(&graph, 2, 0).duration(), 15);
+        assert_eq!(graph.critical_path(step_duration(0)).unwrap().length(), 14);
+    }
+
+    #[test]
+    fn step_names() {
+        assert_eq!(letter_position("A"), Some(1));
+        assert_eq!(letter_position("Z"), Some(26));
+        assert_eq!(letter_position("dig"), None);
+        assert_eq!(letter_position("1"), None);
+        assert_eq!(
+            graph("Step A must be finished before step dig can begin.").err(),
+            Some("step 'dig' is not a single uppercase letter".to_string())
+        );
+        assert!(graph("Step A must finish before step B").is_err());
     }
 }
 
-// common::read_main!();
+/// Instead of the answer `--timeline` prints who worked on which step and when,
+/// `--critical-path` prints how early and late every step can start with unlimited workers and
+/// `--dot` prints the steps as a Graphviz graph labelled with their order and schedule
+fn main() {
+    let input = input_fetcher::fetch_or_exit(7);
+    let graph = match graph(&input) {
+        Ok(graph) => graph,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    };
+    let flag = |name: &str| env::args().any(|a| a == name);
+
+    if flag("--timeline") {
+        print!("{}", schedule(&graph, WORKERS, BASE_DURATION));
+    } else if flag("--dot") {
+        let order = graph.order(TieBreak::Alphabetical).unwrap();
+        let timeline = schedule(&graph, WORKERS, BASE_DURATION);
+        print!(
+            "{}",
+            graph.dot().with_order(&order).with_timeline(&timeline)
+        );
+    } else if flag("--critical-path") {
+        let analysis = graph.critical_path(step_duration(BASE_DURATION)).unwrap();
+        let duration = schedule(&graph, WORKERS, BASE_DURATION).duration();
+        print!("{}", analysis);
+        println!(
+            "{} workers take {}, unlimited workers would take {}",
+            WORKERS,
+            duration,
+            analysis.length()
+        );
+    } else {
+        println!("{}", solve(&graph));
+    }
+}
 
-common::bootstrap!(7);
+// common::read_main!();
+//common::bootstrap!(7);
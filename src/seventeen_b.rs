@@ -1,20 +1,29 @@
 #![feature(try_trait)]
 
+mod water_tile;
+
 use std::{num::ParseIntError, ops::RangeInclusive, option::NoneError, str::FromStr};
 
 use regex::Regex;
 
+use self::water_tile::{Tile, WaterPath};
+use common::frame_render::{Canvas, Error, Recorder};
+
 fn solve(input: &str) -> usize {
+    solve_recorded(input, Recorder::disabled()).expect("a disabled recorder writes nothing")
+}
+
+fn solve_recorded(input: &str, mut frames: Recorder) -> Result<usize, Error> {
     let mut world: World = input.parse().unwrap();
-    world.fill();
-    world
+    world.fill(&mut frames)?;
+    Ok(world
         .tiles
         .into_iter()
         .filter(|t| match t {
             Tile::Water(WaterPath::Settled) => true,
             _ => false,
         })
-        .count()
+        .count())
 }
 
 #[derive(Debug)]
@@ -25,13 +34,19 @@ struct World {
 }
 
 impl World {
-    fn fill(&mut self) {
+    fn fill(&mut self, frames: &mut Recorder) -> Result<(), Error> {
         let mut queue = vec![self.spring_x];
 
         while let Some(tile) = queue.pop() {
             let next = self.drip(tile);
             queue.extend(next.iter().filter_map(|n| *n));
+            frames.record(|| self.canvas())?;
         }
+        Ok(())
+    }
+
+    fn canvas(&self) -> Canvas {
+        Canvas::from_tiles(&self.tiles, self.cols)
     }
 
     fn drip(&mut self, index: usize) -> [Option<usize>; 3] {
@@ -97,46 +112,6 @@ impl World {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Tile {
-    Sand,
-    Water(WaterPath),
-    Clay,
-}
-
-#[derive(Debug, Clone, Copy)]
-enum WaterPath {
-    Down,
-    Sides,
-    Left,
-    Right,
-    Settled,
-}
-
-impl WaterPath {
-    fn blocked_down(&mut self) {
-        if let WaterPath::Down = self {
-            *self = WaterPath::Sides;
-        }
-    }
-
-    fn blocked_right(&mut self) {
-        match self {
-            WaterPath::Sides => *self = WaterPath::Left,
-            WaterPath::Right => *self = WaterPath::Settled,
-            _ => {}
-        }
-    }
-
-    fn blocked_left(&mut self) {
-        match self {
-            WaterPath::Sides => *self = WaterPath::Right,
-            WaterPath::Left => *self = WaterPath::Settled,
-            _ => {}
-        }
-    }
-}
-
 #[derive(Debug)]
 enum ClayGroup {
     Horizontal(usize, RangeInclusive<usize>),
@@ -269,7 +244,7 @@ y=13, x=498..504";
     }
 }
 
-common::read_main!();
+common::record_main!();
 //common::bootstrap!(17);
 
 use std::fmt;
@@ -1,41 +1,21 @@
+mod coord_map;
 mod coord_parser;
 mod extensions;
 
-use self::extensions::{cart_product, AbsDiff, IteratorExt};
+use self::coord_map::CoordMap;
+use common::{
+    input_fetcher,
+    voronoi::{self, Area},
+};
 
-use std::collections::{HashMap, HashSet};
+use std::env;
 
-fn solve(input: &str) -> u16 {
+fn solve(input: &str) -> u64 {
     let coords = coord_parser::parse(input).unwrap();
 
-    let min_x = coords.iter().map(|(c, _)| c).min().cloned().unwrap();
-    let min_y = coords.iter().map(|(_, r)| r).min().cloned().unwrap();
-
-    let max_x = coords.iter().map(|(c, _)| c).max().cloned().unwrap();
-    let max_y = coords.iter().map(|(_, r)| r).max().cloned().unwrap();
-
-    let mut grid: HashMap<(u16, u16), u16> = HashMap::with_capacity(coords.len());
-    let mut infinite_points: HashSet<(u16, u16)> = HashSet::new();
-
-    cart_product(min_x..=max_x, min_y..=max_y)
-        .filter_map(|(c, r)| {
-            coords
-                .iter()
-                .uniq_min_by_key(|(c1, r1)| c1.abs_diff(c) + r1.abs_diff(r))
-                .cloned()
-                .map(|closest| (c, r, closest))
-        })
-        .for_each(|(c, r, closest)| {
-            if c == min_x || c == max_x || r == min_y || r == max_y {
-                infinite_points.insert(closest);
-            } else {
-                *(grid.entry(closest).or_default()) += 1;
-            }
-        });
-
-    grid.into_iter()
-        .filter(|(k, _)| !infinite_points.contains(k))
-        .map(|(_, v)| v)
+    voronoi::areas(&coords)
+        .into_iter()
+        .filter_map(Area::finite)
         .max()
         .unwrap()
 }
@@ -57,4 +37,17 @@ mod tests {
     }
 }
 
-common::bootstrap!(6);
+/// `--map` prints the map of closest coordinates instead of the answer
+fn main() {
+    let input = input_fetcher::fetch_or_exit(6);
+    if env::args().any(|a| a == "--map") {
+        print!(
+            "{}",
+            CoordMap::new(coord_parser::parse(&input).unwrap(), None)
+        );
+    } else {
+        println!("{}", solve(&input));
+    }
+}
+
+//common::bootstrap!(6);
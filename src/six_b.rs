@@ -1,36 +1,21 @@
+mod coord_map;
 mod coord_parser;
 mod extensions;
 
-use self::extensions::{cart_product, AbsDiff};
+use self::coord_map::CoordMap;
+use common::{input_fetcher, safe_region::region_size};
 
-fn solve(input: &str) -> usize {
-    region(input, 10_000)
+use std::env;
+
+const SAFE_DISTANCE: u64 = 10_000;
+
+fn solve(input: &str) -> u64 {
+    region(input, SAFE_DISTANCE)
 }
 
-fn region(input: &str, max_distance: u16) -> usize {
+fn region(input: &str, max_distance: u64) -> u64 {
     let coords = coord_parser::parse(input).unwrap();
-
-    let min_x = coords.iter().map(|(c, _)| c).min().cloned().unwrap();
-    let min_y = coords.iter().map(|(_, r)| r).min().cloned().unwrap();
-
-    let max_x = coords.iter().map(|(c, _)| c).max().cloned().unwrap();
-    let max_y = coords.iter().map(|(_, r)| r).max().cloned().unwrap();
-
-    let x_start = max_x as i16 - max_distance as i16;
-    let x_end = min_x as i16 + max_distance as i16;
-    let y_start = max_y as i16 - max_distance as i16;
-    let y_end = min_y as i16 + max_distance as i16;
-
-    cart_product(x_start..=x_end, y_start..=y_end)
-        .map(|(c, r)| {
-            coords
-                .iter()
-                .map(|(c1, r1)| c1.abs_diff(c) + r1.abs_diff(r))
-                .map(u32::from)
-                .sum::<u32>()
-        })
-        .filter(|&d| d < u32::from(max_distance))
-        .count()
+    region_size(&coords, max_distance)
 }
 
 #[cfg(test)]
@@ -50,5 +35,16 @@ mod tests {
     }
 }
 
+/// `--map` prints the map of closest coordinates with the safe region drawn over it
+fn main() {
+    let input = input_fetcher::fetch_or_exit(6);
+    if env::args().any(|a| a == "--map") {
+        let coords = coord_parser::parse(&input).unwrap();
+        print!("{}", CoordMap::new(coords, Some(SAFE_DISTANCE as i64)));
+    } else {
+        println!("{}", solve(&input));
+    }
+}
+
 //common::read_main!();
-common::bootstrap!(6);
+//common::bootstrap!(6);
@@ -1,3 +1,4 @@
+use common::star_alignment::{self, Star};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::str::FromStr;
@@ -8,19 +9,6 @@ struct Point {
     velocity: (i32, i32),
 }
 
-struct Message {
-    points: Vec<Point>,
-}
-
-impl Message {
-    fn advance(&mut self) {
-        self.points.iter_mut().for_each(|p| {
-            p.position.0 += p.velocity.0;
-            p.position.1 += p.velocity.1;
-        })
-    }
-}
-
 impl FromStr for Point {
     type Err = Box<std::error::Error>;
 
@@ -46,29 +34,60 @@ impl FromStr for Point {
     }
 }
 
-fn solve(input: &str) -> u32 {
-    let mut message = input
+fn solve(input: &str) -> i64 {
+    let stars = input
         .lines()
         .map(Point::from_str)
         .collect::<Result<Vec<_>, _>>()
-        .map(|points| Message { points })
-        .unwrap();
-
-    (0..)
-        .scan(999_999, |width, second| {
-            message.advance();
-            let min_x = message.points.iter().map(|p| p.position.0).min().unwrap();
-            let max_x = message.points.iter().map(|p| p.position.0).max().unwrap();
-            let new_width = max_x - min_x;
-            if *width < new_width {
-                None
-            } else {
-                *width = new_width;
-                Some(second + 1)
-            }
-        })
-        .last()
         .unwrap()
+        .into_iter()
+        .map(|p| Star {
+            position: (i64::from(p.position.0), i64::from(p.position.1)),
+            velocity: (i64::from(p.velocity.0), i64::from(p.velocity.1)),
+        })
+        .collect::<Vec<_>>();
+    star_alignment::convergence_time(&stars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample() {
+        let input = r"position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>";
+        assert_eq!(solve(input), 3);
+    }
 }
 
 common::read_main!();
@@ -1,14 +1,9 @@
+mod track;
+
 use std::collections::HashSet;
 
-#[derive(Clone, Copy, Debug)]
-enum Track {
-    Horizontal,
-    Vertical,
-    LeftCurve,
-    RightCurve,
-    Intersection,
-    Empty,
-}
+use self::track::Track;
+use common::frame_render::{Canvas, Error, Recorder};
 
 #[derive(Debug)]
 struct Cart {
@@ -105,6 +100,10 @@ impl Turn {
 }
 
 fn solve(input: &str) -> Coord {
+    solve_recorded(input, Recorder::disabled()).expect("a disabled recorder writes nothing")
+}
+
+fn solve_recorded(input: &str, mut frames: Recorder) -> Result<Coord, Error> {
     let mut lines = input.lines();
     let cols = lines.next().unwrap().len();
     let rows = lines.count() + 1;
@@ -158,6 +157,7 @@ fn solve(input: &str) -> Coord {
 
     let mut coords: HashSet<_> = carts.iter().map(|c| c.position).collect();
     loop {
+        frames.record(|| canvas(&map, cols, &carts))?;
         carts.sort_by_key(|c| c.position);
         let crash = carts.iter_mut().find_map(|c| {
             coords.remove(&c.position);
@@ -181,11 +181,19 @@ fn solve(input: &str) -> Coord {
             }
         });
         if let Some(p) = crash {
-            break p;
+            break Ok(p);
         }
     }
 }
 
+fn canvas(map: &[Track], cols: usize, carts: &[Cart]) -> Canvas {
+    let mut canvas = Canvas::from_tiles(map, cols);
+    carts.iter().for_each(|c| {
+        canvas.paint(c.position.x, c.position.y, [0xe0, 0x20, 0x20]);
+    });
+    canvas
+}
+
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Clone, Copy, Hash)]
 struct Coord {
     y: usize,
@@ -216,5 +224,5 @@ mod tests {
     }
 }
 
-common::read_main!();
+common::record_main!();
 //common::bootstrap!(12);
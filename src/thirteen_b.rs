@@ -1,14 +1,9 @@
+mod track;
+
 use std::collections::HashSet;
 
-#[derive(Clone, Copy, Debug)]
-enum Track {
-    Horizontal,
-    Vertical,
-    LeftCurve,
-    RightCurve,
-    Intersection,
-    Empty,
-}
+use self::track::Track;
+use common::frame_render::{Canvas, Error, Recorder};
 
 #[derive(Debug)]
 struct Cart {
@@ -105,6 +100,10 @@ impl Turn {
 }
 
 fn solve(input: &str) -> Coord {
+    solve_recorded(input, Recorder::disabled()).expect("a disabled recorder writes nothing")
+}
+
+fn solve_recorded(input: &str, mut frames: Recorder) -> Result<Coord, Error> {
     let mut lines = input.lines();
     let cols = lines.next().unwrap().len();
     let rows = lines.count() + 1;
@@ -158,6 +157,7 @@ fn solve(input: &str) -> Coord {
 
     let mut coords: HashSet<_> = carts.iter().map(|c| c.position).collect();
     let last_cart = loop {
+        frames.record(|| canvas(&map, cols, &carts))?;
         carts.sort_by_key(|c| c.position);
         let mut start = 0;
         while let Some((coord, i)) = find_crash(&mut carts, &mut coords, &map, cols, start) {
@@ -174,7 +174,7 @@ fn solve(input: &str) -> Coord {
         }
     };
 
-    last_cart.position
+    Ok(last_cart.position)
 }
 
 fn find_crash(
@@ -207,6 +207,14 @@ fn find_crash(
     })
 }
 
+fn canvas(map: &[Track], cols: usize, carts: &[Cart]) -> Canvas {
+    let mut canvas = Canvas::from_tiles(map, cols);
+    carts.iter().for_each(|c| {
+        canvas.paint(c.position.x, c.position.y, [0xe0, 0x20, 0x20]);
+    });
+    canvas
+}
+
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Clone, Copy, Hash)]
 struct Coord {
     y: usize,
@@ -238,5 +246,5 @@ mod tests {
     }
 }
 
-common::read_main!();
+common::record_main!();
 //common::bootstrap!(12);
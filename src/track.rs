@@ -0,0 +1,22 @@
+use common::frame_render::{Palette, Rgb};
+
+/// A square of the day 13 track map
+#[derive(Clone, Copy, Debug)]
+pub enum Track {
+    Horizontal,
+    Vertical,
+    LeftCurve,
+    RightCurve,
+    Intersection,
+    Empty,
+}
+
+impl Palette for Track {
+    fn color(&self) -> Rgb {
+        match self {
+            Track::Empty => [0x10, 0x10, 0x10],
+            Track::Intersection => [0xff, 0xff, 0xff],
+            _ => [0x90, 0x90, 0x90],
+        }
+    }
+}
@@ -1,32 +1,13 @@
-use std::collections::HashSet;
+use common::box_index::SimilarityIndex;
 
 fn solve(input: &str) -> String {
-    let mut previous: HashSet<&str> = HashSet::new();
-
-    for id in input.lines() {
-        match previous.iter().find(|old_id| {
-            old_id
-                .chars()
-                .zip(id.chars())
-                .filter(|(a, b)| a != b)
-                .take(2)
-                .count()
-                == 1
-        }) {
-            Some(old_id) => {
-                return old_id
-                    .chars()
-                    .zip(id.chars())
-                    .filter_map(|(a, b)| if a == b { Some(a) } else { None })
-                    .collect();
-            }
-            None => {
-                previous.insert(id);
-            }
-        }
-    }
-
-    "".into()
+    SimilarityIndex::new(input.lines(), 1)
+        .signature_groups()
+        .unwrap()
+        .into_iter()
+        .map(|(common, _)| common)
+        .next()
+        .unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -44,6 +25,15 @@ axcye
 wvxyz"#;
         assert_eq!(solve(input), "fgij".to_string())
     }
+
+    #[test]
+    fn first_match_in_input() {
+        let input = r#"zzzz
+zzzy
+aaaa
+aaab"#;
+        assert_eq!(solve(input), "zzz".to_string())
+    }
 }
 
 common::bootstrap!(2);
@@ -0,0 +1,53 @@
+use common::frame_render::{Palette, Rgb};
+
+/// A square of the day 17 ground slice
+#[derive(Debug, Clone, Copy)]
+pub enum Tile {
+    Sand,
+    Water(WaterPath),
+    Clay,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum WaterPath {
+    Down,
+    Sides,
+    Left,
+    Right,
+    Settled,
+}
+
+impl WaterPath {
+    pub fn blocked_down(&mut self) {
+        if let WaterPath::Down = self {
+            *self = WaterPath::Sides;
+        }
+    }
+
+    pub fn blocked_right(&mut self) {
+        match self {
+            WaterPath::Sides => *self = WaterPath::Left,
+            WaterPath::Right => *self = WaterPath::Settled,
+            _ => {}
+        }
+    }
+
+    pub fn blocked_left(&mut self) {
+        match self {
+            WaterPath::Sides => *self = WaterPath::Right,
+            WaterPath::Left => *self = WaterPath::Settled,
+            _ => {}
+        }
+    }
+}
+
+impl Palette for Tile {
+    fn color(&self) -> Rgb {
+        match self {
+            Tile::Clay => [0x8b, 0x45, 0x13],
+            Tile::Sand => [0xf4, 0xe4, 0xbc],
+            Tile::Water(WaterPath::Settled) => [0x1e, 0x3f, 0xa8],
+            Tile::Water(_) => [0x6c, 0xb4, 0xee],
+        }
+    }
+}